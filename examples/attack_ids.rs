@@ -0,0 +1,85 @@
+//! Generates the list of known ATT&CK IDs (`src/validators/attack_ids.txt`) from the
+//! MITRE ATT&CK STIX bundles (https://github.com/mitre/cti), eg:
+//!   cargo run --example attack_ids -- enterprise-attack.json mobile-attack.json \
+//!       ics-attack.json > src/validators/attack_ids.txt
+use serde_json::Value;
+use std::{collections::BTreeSet, env, fs::read_to_string, process::exit};
+
+/// The STIX objects that have an ATT&CK ID: techniques, tactics, groups, software & mitigations
+const TYPES: [&str; 6] = [
+    "attack-pattern",
+    "x-mitre-tactic",
+    "intrusion-set",
+    "malware",
+    "tool",
+    "course-of-action",
+];
+
+fn main() {
+    let files: Vec<String> = env::args().skip(1).collect();
+    if files.is_empty() {
+        println!("please give the ATT&CK STIX bundles to list the IDs of!");
+        exit(1);
+    }
+
+    let mut versions = vec![];
+    let mut ids = BTreeSet::new();
+    for file in &files {
+        let bundle: Value = match read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|x| serde_json::from_str(&x).map_err(|e| e.to_string()))
+        {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                eprintln!("{}: {}", file, e);
+                exit(1);
+            }
+        };
+        for object in bundle["objects"].as_array().into_iter().flatten() {
+            let kind = object["type"].as_str().unwrap_or_default();
+            if kind == "x-mitre-collection" {
+                versions.push(format!(
+                    "{} v{}",
+                    object["name"].as_str().unwrap_or(file),
+                    object["x_mitre_version"].as_str().unwrap_or("?")
+                ));
+                continue;
+            }
+            // revoked & deprecated objects are no longer part of ATT&CK
+            if !TYPES.contains(&kind)
+                || object["revoked"].as_bool() == Some(true)
+                || object["x_mitre_deprecated"].as_bool() == Some(true)
+            {
+                continue;
+            }
+            for reference in object["external_references"]
+                .as_array()
+                .into_iter()
+                .flatten()
+            {
+                let source = reference["source_name"].as_str().unwrap_or_default();
+                if let (true, Some(id)) = (
+                    source.starts_with("mitre-") && source.ends_with("attack"),
+                    reference["external_id"].as_str(),
+                ) {
+                    ids.insert(id.to_string());
+                }
+            }
+        }
+    }
+
+    println!("# MITRE ATT&CK Identifiers known to `attack::is_known_attack_id`");
+    println!("#");
+    println!("# Generated with `cargo run --example attack_ids` from:");
+    for version in versions {
+        println!("#   {}", version);
+    }
+    println!("# without the revoked & deprecated objects; one ID per line.");
+    println!("# Sub-techniques (eg: T1059.001) are known when their parent technique is known,");
+    println!("# unless sub-techniques of that parent are listed explicitly.");
+    println!("# Lines starting with '#' are ignored.");
+    println!();
+    for id in ids {
+        println!("{}", id);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    indicator::Category,
+    validators::network::{is_in_network, split_host_port},
+};
+use fancy_regex::Regex;
+use std::{
+    collections::HashSet,
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+    net::IpAddr,
+    path::Path,
+};
+
+/// Entries of one kind of list
+#[derive(Debug, Clone, Default)]
+struct Entries {
+    exact: HashSet<String>,
+    suffixes: Vec<String>,
+    networks: Vec<(IpAddr, u8)>,
+    regexes: Vec<Regex>,
+}
+
+impl Entries {
+    fn is_empty(&self) -> bool {
+        self.exact.is_empty()
+            && self.suffixes.is_empty()
+            && self.networks.is_empty()
+            && self.regexes.is_empty()
+    }
+
+    fn matches(&self, value: &str, hosts: &[&str], overlap: bool) -> bool {
+        //! Checks the value & the host names or addresses in it against the entries; a
+        //! network (eg: `10.0.0.0/16`) matches a network entry when it is inside it, or
+        //! with `overlap` when they share any address
+        let candidates = || std::iter::once(value).chain(hosts.iter().copied());
+        candidates().any(|x| self.exact.contains(&x.to_lowercase()))
+            || hosts.iter().any(|host| {
+                let host = host.to_lowercase();
+                self.suffixes
+                    .iter()
+                    .any(|suffix| host == suffix[1..] || host.ends_with(suffix.as_str()))
+                    || range(&host).is_some_and(|(ip, length)| {
+                        self.networks.iter().any(|(network, prefix)| {
+                            (*prefix <= length && is_in_network(ip, *network, *prefix))
+                                || (overlap && is_in_network(*network, ip, length))
+                        })
+                    })
+            })
+            || candidates().any(|x| {
+                self.regexes
+                    .iter()
+                    .any(|regex| regex.is_match(x).unwrap_or_default())
+            })
+    }
+}
+
+fn range(host: &str) -> Option<(IpAddr, u8)> {
+    //! The address & prefix length of an IP Address or network, eg: `10.0.0.0/8`
+    let (ip, prefix) = match host.split_once('/') {
+        Some((ip, prefix)) => (ip.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+        None => (host.parse::<IpAddr>().ok()?, None),
+    };
+    let max = if ip.is_ipv4() { 32 } else { 128 };
+    Some((ip, prefix.unwrap_or(max).min(max)))
+}
+
+fn hosts(category: Category, value: &str) -> Vec<&str> {
+    //! The host names or addresses in a value of the given category
+    match category {
+        Category::Domains | Category::IpAddress => vec![value],
+        Category::Endpoints => split_host_port(value).map(|x| x.0).into_iter().collect(),
+        Category::Emails => value.rsplit_once('@').map(|x| x.1).into_iter().collect(),
+        Category::Urls => {
+            let rest = value.split_once("://").map_or(value, |x| x.1);
+            let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+            let host = authority.rsplit_once('@').map_or(authority, |x| x.1);
+            let host = match host.strip_prefix('[') {
+                Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+                None => host.split(':').next().unwrap_or_default(),
+            };
+            vec![host]
+        }
+        // "value - kind"
+        Category::Crypto
+        | Category::CryptoSecrets
+        | Category::Secrets
+        | Category::Blocks
+        | Category::Hashes => value.rsplit_once(" - ").map(|x| x.0).into_iter().collect(),
+        _ => vec![],
+    }
+}
+
+/// Values that are never reported as indicators, eg: well-known benign domains.
+///
+/// A list has one entry per line; empty lines & lines starting with `#` are skipped:
+/// - `microsoft.com`: the exact value (case-insensitive); also matches the host of URLs & emails
+/// - `*.microsoft.com`: the domain and all its sub domains, also in URLs & emails
+/// - `192.0.2.0/24`: the IP Addresses & networks within the network, also in URLs
+/// - `/^[0-9a-f]{32}$/`: the values matching the regular expression
+/// - `!evil.microsoft.com`: never suppressed, even if another entry matches (a blocklist)
+///
+/// ## Example Usage
+/// ```rust
+/// use ioc_extract::{Allowlist, Extractor};
+///
+/// let mut allowlist = Allowlist::bundled();
+/// allowlist.add("*.corp.local").unwrap();
+/// let ioc = Extractor::new()
+///     .allowlist(allowlist)
+///     .extract("http://schemas.xmlsoap.org/soap/ www.evil-domain.com 8.8.8.8 192.0.2.1")
+///     .unwrap();
+/// assert_eq!(ioc.domains.unwrap(), vec!["www.evil-domain.com"]);
+/// assert!(ioc.urls.is_none());
+/// assert!(ioc.ip_address.is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    allowed: Entries,
+    blocked: Entries,
+}
+
+impl Allowlist {
+    pub fn new() -> Self {
+        //! An empty list
+        Self::default()
+    }
+
+    pub fn bundled() -> Self {
+        //! The bundled list of well-known benign domains (example & reserved names, schemas,
+        //! vendors), public DNS resolvers and documentation ranges
+        let mut allowlist = Self::new();
+        allowlist
+            .add_list(include_str!("allowlist.txt"))
+            .expect("bundled allowlist");
+        allowlist
+    }
+
+    pub fn add(&mut self, entry: &str) -> Result<()> {
+        //! Adds a single entry
+        let invalid = |e: String| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid allowlist entry: {}: {}", entry, e),
+            )
+        };
+        let (entries, entry) = match entry.strip_prefix('!') {
+            Some(entry) => (&mut self.blocked, entry),
+            None => (&mut self.allowed, entry),
+        };
+        if let Some(pattern) = entry
+            .strip_prefix('/')
+            .and_then(|x| x.strip_suffix('/'))
+            .filter(|x| !x.is_empty())
+        {
+            let regex = Regex::new(pattern).map_err(|e| invalid(e.to_string()))?;
+            entries.regexes.push(regex);
+        } else if let Some(domain) = entry.strip_prefix("*.") {
+            entries.suffixes.push(format!(".{}", domain.to_lowercase()));
+        } else if let Some((network, prefix)) = entry.split_once('/') {
+            let network: IpAddr = network.parse().map_err(|_| invalid("not an IP".into()))?;
+            let max = if network.is_ipv4() { 32 } else { 128 };
+            let prefix = prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|x| *x <= max)
+                .ok_or_else(|| invalid("prefix length out of range".into()))?;
+            entries.networks.push((network, prefix));
+        } else if entry.is_empty() {
+            return Err(invalid("empty".into()));
+        } else {
+            entries.exact.insert(entry.to_lowercase());
+        }
+        Ok(())
+    }
+
+    pub fn add_list(&mut self, list: &str) -> Result<()> {
+        //! Adds the entries of a list; one entry per line
+        for line in list.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            self.add(line)?;
+        }
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        //! Adds the entries of a list file
+        self.add_list(&read_to_string(path)?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        //! Checks to see if nothing would be suppressed by the list
+        self.allowed.is_empty()
+    }
+
+    pub fn is_allowed(&self, category: Category, value: &str) -> bool {
+        //! Checks to see if a value of the given category is on the list, so is not an indicator
+        let hosts = hosts(category, value);
+        self.allowed.matches(value, &hosts, false) && !self.blocked.matches(value, &hosts, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_allowed() {
+        let mut allowlist = Allowlist::new();
+        allowlist
+            .add_list("# comment\nmicrosoft.com\n*.w3.org\n10.0.0.0/8\n/^T1059/\n!evil.w3.org\n")
+            .unwrap();
+        assert!(allowlist.is_allowed(Category::Domains, "Microsoft.com"));
+        assert!(!allowlist.is_allowed(Category::Domains, "www.microsoft.com"));
+        assert!(allowlist.is_allowed(Category::Urls, "https://microsoft.com/x"));
+        assert!(allowlist.is_allowed(Category::Emails, "john@microsoft.com"));
+        assert!(allowlist.is_allowed(Category::Domains, "w3.org"));
+        assert!(allowlist.is_allowed(Category::Urls, "http://www.w3.org/2001/XMLSchema"));
+        assert!(!allowlist.is_allowed(Category::Domains, "evil.w3.org"));
+        assert!(!allowlist.is_allowed(Category::Domains, "notw3.org"));
+        assert!(allowlist.is_allowed(Category::IpAddress, "10.1.2.3"));
+        assert!(allowlist.is_allowed(Category::IpAddress, "10.0.0.0/16"));
+        assert!(allowlist.is_allowed(Category::IpAddress, "10.0.0.0/8"));
+        assert!(!allowlist.is_allowed(Category::IpAddress, "10.0.0.0/4"));
+        assert!(!allowlist.is_allowed(Category::IpAddress, "0.0.0.0/0"));
+        assert!(allowlist.is_allowed(Category::Urls, "http://user@10.1.2.3:8080/a"));
+        assert!(!allowlist.is_allowed(Category::IpAddress, "11.1.2.3"));
+        assert!(allowlist.is_allowed(Category::Endpoints, "10.1.2.3:443"));
+        assert!(allowlist.is_allowed(Category::Endpoints, "www.w3.org:80"));
+        assert!(allowlist.is_allowed(Category::AttackIds, "T1059.001"));
+    }
+
+    #[test]
+    fn test_networks() {
+        let mut allowlist = Allowlist::new();
+        allowlist
+            .add_list(
+                "10.0.0.0/8
+!10.1.0.0/16
+",
+            )
+            .unwrap();
+        assert!(allowlist.is_allowed(Category::IpAddress, "10.2.0.0/16"));
+        assert!(!allowlist.is_allowed(Category::IpAddress, "10.1.2.3"));
+        assert!(!allowlist.is_allowed(Category::IpAddress, "10.1.2.0/24"));
+        assert!(
+            !allowlist.is_allowed(Category::IpAddress, "10.0.0.0/8"),
+            "a network with blocked addresses is not suppressed"
+        );
+    }
+
+    #[test]
+    fn test_bundled() {
+        let allowlist = Allowlist::bundled();
+        assert!(allowlist.is_allowed(Category::Domains, "schemas.xmlsoap.org"));
+        assert!(allowlist.is_allowed(Category::IpAddress, "8.8.8.8"));
+        assert!(allowlist.is_allowed(Category::IpAddress, "2001:db8::1"));
+        assert!(allowlist.is_allowed(Category::Urls, "http://[2001:db8::1]/x"));
+        assert!(!allowlist.is_allowed(Category::Domains, "www.evil-domain.com"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut allowlist = Allowlist::new();
+        assert!(allowlist.add("10.0.0.0/33").is_err());
+        assert!(allowlist.add("x.com/8").is_err());
+        assert!(allowlist.add("/(/").is_err());
+        assert!(allowlist.add("!").is_err());
+        assert!(allowlist.is_empty());
+    }
+}
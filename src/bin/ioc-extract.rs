@@ -0,0 +1,348 @@
+//! Extracts indicators from files, directories or stdin
+//!
+//! Exits with 0 if indicators were found, 1 if none were found and 2 on errors.
+use clap::{Parser, ValueEnum};
+use ioc_extract::{
+    export::csv::Csv, Allowlist, Artifacts, Category, Extractor, Indicator, IpClass, Match,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufReader, BufWriter, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// All the found indicators as one JSON object, by category
+    Json,
+    /// One JSON object per indicator & input
+    Jsonl,
+    /// One row per indicator & input: source,category,value
+    Csv,
+    /// One indicator per line
+    Text,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "ioc-extract",
+    version,
+    about = "Extract indicators like urls, domains, ips, emails, hashes, etc... from files, directories or stdin"
+)]
+struct Args {
+    /// Files or directories (searched recursively) to extract from; stdin if none are given, or `-`
+    paths: Vec<PathBuf>,
+    /// Extract only these categories, eg: urls,domains,ip_address
+    #[arg(short, long, value_delimiter = ',', value_parser = parse_category)]
+    categories: Vec<Category>,
+    /// Do not extract these categories
+    #[arg(short = 'x', long, value_delimiter = ',', value_parser = parse_category)]
+    exclude: Vec<Category>,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    /// Maximum number of indicators per category, per input
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Skip words & lines longer than this many bytes
+    #[arg(long)]
+    max_length: Option<usize>,
+    /// Keep only the known MITRE ATT&CK IDs
+    #[arg(long)]
+    known_attack_ids: bool,
+    /// Read the inputs as binary (ASCII & UTF-16LE strings); files that are not valid UTF-8 always are
+    #[arg(short, long)]
+    binary: bool,
+    /// Minimum number of characters of the strings taken from binary input
+    #[arg(long, default_value_t = 4)]
+    min_string_length: usize,
+    /// Drop the values on this list of benign values; one entry per line
+    #[arg(short, long, value_name = "FILE")]
+    allowlist: Vec<PathBuf>,
+    /// Drop the well-known benign domains & addresses of the bundled list
+    #[arg(long)]
+    default_allowlist: bool,
+    /// Keep only the IP Addresses of these classes, eg: public,cgnat
+    #[arg(long, value_delimiter = ',', value_parser = parse_ip_class)]
+    ip_class: Vec<IpClass>,
+    /// Mask the found secrets (API keys, tokens, private keys & wallet secrets) in the output
+    #[arg(long)]
+    redact: bool,
+}
+
+/// The typed Indicators found, by category & value as kept in `Artifacts`; so sorted &
+/// de-duplicated as in `Artifacts`
+type Found = BTreeMap<(Category, String), Indicator>;
+
+/// An indicator of a JSON Lines output, along with where it was found
+#[derive(Serialize)]
+struct Row<'a> {
+    source: &'a str,
+    #[serde(flatten)]
+    indicator: &'a Indicator,
+}
+
+fn parse_category(name: &str) -> Result<Category, String> {
+    Category::all()
+        .into_iter()
+        .find(|c| c.name() == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = Category::all().iter().map(|c| c.name()).collect();
+            format!("expected one of: {}", names.join(", "))
+        })
+}
+
+fn parse_ip_class(name: &str) -> Result<IpClass, String> {
+    IpClass::all()
+        .into_iter()
+        .find(|c| c.name() == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = IpClass::all().iter().map(|c| c.name()).collect();
+            format!("expected one of: {}", names.join(", "))
+        })
+}
+
+fn extractor(args: &Args) -> io::Result<Extractor> {
+    let mut allowlist = if args.default_allowlist {
+        Allowlist::bundled()
+    } else {
+        Allowlist::new()
+    };
+    for path in &args.allowlist {
+        allowlist
+            .load(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    }
+    let mut extractor = Extractor::new()
+        .known_attack_ids(args.known_attack_ids)
+        .min_string_length(args.min_string_length)
+        .allowlist(allowlist)
+        .redact_secrets(args.redact);
+    if !args.categories.is_empty() {
+        extractor = extractor.categories(&args.categories);
+    }
+    for category in &args.exclude {
+        extractor = extractor.disable(*category);
+    }
+    if !args.ip_class.is_empty() {
+        extractor = extractor.ip_classes(&args.ip_class);
+    }
+    if let Some(limit) = args.limit {
+        extractor = extractor.limit(limit);
+    }
+    if let Some(max_length) = args.max_length {
+        extractor = extractor.max_length(max_length);
+    }
+    Ok(extractor)
+}
+
+fn walk(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    //! All the files in a directory & its sub directories, in order
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            walk(&entry, files)?;
+        } else {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn add(found: &mut Found, category: Category, m: Match) {
+    //! Keeps the typed Indicator of a Match, as given by the extraction
+    if let Some(indicator) = m.indicator {
+        found.entry((category, m.value)).or_insert(indicator);
+    }
+}
+
+fn extract(extractor: &Extractor, path: &Path, binary: bool) -> io::Result<Found> {
+    let mut found = Found::new();
+    if path == Path::new("-") {
+        let stdin = io::stdin().lock();
+        if binary {
+            extractor.stream_bytes(stdin, |c, m| add(&mut found, c, m))?;
+        } else {
+            extractor
+                .stream(stdin, |c, m| add(&mut found, c, m))
+                .map_err(|e| match e.kind() {
+                    ErrorKind::InvalidData => io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{} (use --binary for binary input)", e),
+                    ),
+                    _ => e,
+                })?;
+        }
+        return Ok(found);
+    }
+    let file = BufReader::new(fs::File::open(path)?);
+    if binary {
+        extractor.stream_bytes(file, |c, m| add(&mut found, c, m))?;
+        return Ok(found);
+    }
+    // files that are not valid UTF-8 are read as binary input, as by `extract_file`
+    match extractor.stream(file, |c, m| add(&mut found, c, m)) {
+        Err(e) if e.kind() == ErrorKind::InvalidData => {
+            found.clear();
+            let file = BufReader::new(fs::File::open(path)?);
+            extractor.stream_bytes(file, |c, m| add(&mut found, c, m))?;
+        }
+        result => result?,
+    }
+    Ok(found)
+}
+
+fn value(indicator: &Indicator) -> String {
+    //! The indicator as written in the line based formats; networks keep their CIDR suffix
+    match indicator {
+        Indicator::Ip {
+            value,
+            cidr: Some(cidr),
+            ..
+        } => format!("{}/{}", value, cidr),
+        _ => indicator.value().to_string(),
+    }
+}
+
+fn write_rows<'a, W, I>(out: &mut W, format: Format, source: &str, indicators: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Indicator>,
+{
+    //! Writes the indicators found in one input, for the line based formats
+    for indicator in indicators {
+        match format {
+            Format::Jsonl => {
+                let row = Row { source, indicator };
+                serde_json::to_writer(&mut *out, &row)?;
+                writeln!(out)?;
+            }
+            Format::Csv => writeln!(
+                out,
+                "{}",
+                Csv::new().row(&[source, indicator.category().name(), &value(indicator)])
+            )?,
+            Format::Json | Format::Text => {}
+        }
+    }
+    Ok(())
+}
+
+fn run<W: Write>(args: &Args, out: &mut W) -> io::Result<ExitCode> {
+    let extractor = extractor(args)?;
+    let mut paths = vec![];
+    let mut failed = false;
+    for path in &args.paths {
+        if path.is_dir() {
+            if let Err(e) = walk(path, &mut paths) {
+                eprintln!("ioc-extract: {}: {}", path.display(), e);
+                failed = true;
+            }
+        } else {
+            paths.push(path.clone());
+        }
+    }
+    if args.paths.is_empty() {
+        paths.push(PathBuf::from("-"));
+    }
+
+    if args.format == Format::Csv {
+        writeln!(out, "source,category,value")?;
+    }
+    let mut found = false;
+    let mut all = Found::new();
+    for path in paths {
+        let source = path.display().to_string();
+        match extract(&extractor, &path, args.binary) {
+            Ok(indicators) if !indicators.is_empty() => {
+                found = true;
+                match args.format {
+                    Format::Json | Format::Text => all.extend(indicators),
+                    Format::Jsonl | Format::Csv => {
+                        write_rows(out, args.format, &source, indicators.values())?
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("ioc-extract: {}: {}", source, e);
+                failed = true;
+            }
+        }
+    }
+
+    match args.format {
+        Format::Json => {
+            let artifacts: Artifacts = all.into_values().collect();
+            serde_json::to_writer_pretty(&mut *out, &artifacts)?;
+            writeln!(out)?;
+        }
+        Format::Text => {
+            for indicator in all.values() {
+                writeln!(out, "{}", value(indicator))?;
+            }
+        }
+        Format::Jsonl | Format::Csv => {}
+    }
+    out.flush()?;
+
+    Ok(if failed {
+        ExitCode::from(2)
+    } else if found {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    })
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut out = BufWriter::new(io::stdout().lock());
+    match run(&args, &mut out) {
+        Ok(code) => code,
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ioc-extract: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_category() {
+        assert_eq!(parse_category("ip_address"), Ok(Category::IpAddress));
+        assert!(parse_category("ips").is_err());
+        let args = Args::parse_from(["ioc-extract", "-c", "urls,domains", "-f", "csv"]);
+        assert_eq!(args.categories, vec![Category::Urls, Category::Domains]);
+        assert_eq!(args.format, Format::Csv);
+        let args = Args::parse_from(["ioc-extract", "--ip-class", "public,link_local"]);
+        assert_eq!(args.ip_class, vec![IpClass::Public, IpClass::LinkLocal]);
+        assert!(parse_ip_class("linklocal").is_err());
+    }
+
+    #[test]
+    fn test_write_rows() {
+        let mut found = Found::new();
+        Extractor::new()
+            .stream("see https://www.example.com".as_bytes(), |c, m| {
+                add(&mut found, c, m)
+            })
+            .unwrap();
+        let mut out = vec![];
+        write_rows(&mut out, Format::Jsonl, "a.txt", found.values()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"source\":\"a.txt\",\"type\":\"url\",\"value\":\"https://www.example.com\"}\n"
+        );
+    }
+}
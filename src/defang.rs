@@ -0,0 +1,230 @@
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::net::Ipv6Addr;
+
+lazy_static! {
+    /// hxxp://, hXXps://, h[tt]p://, fxp:// etc...
+    static ref SCHEME: Regex = Regex::new(
+        r"(?i)^(?:h(?:xx|\[xx\]|\[tt\]|\*\*|x)p(s?)|(f)(?:x|\[x\]|\[t\])p(s?))(?=[\[\(\{]?:)"
+    ).unwrap();
+    /// [://], [:]//, (:)//, {:}// etc...
+    static ref SCHEME_SEP: Regex = Regex::new(r"[\[\(\{]:(//)?[\]\)\}](//)?").unwrap();
+    /// [.], (.), {.}, [dot], (DOT), { dot } etc...
+    static ref DOT: Regex = Regex::new(r"(?i)[\[\(\{]\s*(?:\.|dot)\s*[\]\)\}]").unwrap();
+    /// [@], (@), {@}, [at], (AT), { at } etc...
+    static ref AT: Regex = Regex::new(r"(?i)[\[\(\{]\s*(?:@|at)\s*[\]\)\}]").unwrap();
+    /// [/], (/), {/}
+    static ref SLASH: Regex = Regex::new(r"[\[\(\{]/[\]\)\}]").unwrap();
+}
+
+/// Different styles of rendering defanged indicators
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefangStyle {
+    /// `hxxp[://]evil[.]com`, `user[@]evil[.]com`, `10[.]0[.]0[.]1`
+    #[default]
+    Brackets,
+    /// `hxxp(://)evil(.)com`, `user(@)evil(.)com`, `10(.)0(.)0(.)1`
+    Parens,
+    /// `hxxp{://}evil{.}com`, `user{@}evil{.}com`, `10{.}0{.}0{.}1`
+    Braces,
+    /// `hxxp[://]evil[dot]com`, `user[at]evil[dot]com`, `10[dot]0[dot]0[dot]1`
+    Words,
+}
+
+impl DefangStyle {
+    fn wrap(&self, s: &str) -> String {
+        match *self {
+            DefangStyle::Brackets | DefangStyle::Words => format!("[{}]", s),
+            DefangStyle::Parens => format!("({})", s),
+            DefangStyle::Braces => format!("{{{}}}", s),
+        }
+    }
+
+    fn dot(&self) -> String {
+        match *self {
+            DefangStyle::Words => self.wrap("dot"),
+            _ => self.wrap("."),
+        }
+    }
+
+    fn at(&self) -> String {
+        match *self {
+            DefangStyle::Words => self.wrap("at"),
+            _ => self.wrap("@"),
+        }
+    }
+}
+
+pub fn defang(value: &str, style: DefangStyle) -> String {
+    //! Renders a URL, Domain, Email or IP Address in a defanged form that is safe to share.
+    //! Every `.`, `@` & `://` is escaped and the scheme is rewritten (`hxxp`, `hxxps`, `fxp`),
+    //! so the result is never picked up as a live link.
+    //!
+    //! ## Example Usage
+    //! ```rust
+    //! use ioc_extract::{defang, DefangStyle};
+    //!
+    //! assert_eq!(defang("https://evil.com/a.php", DefangStyle::Brackets), "hxxps[://]evil[.]com/a[.]php");
+    //! assert_eq!(defang("user@evil.com", DefangStyle::Words), "user[at]evil[dot]com");
+    //! ```
+    let (scheme, rest) = match value.split_once("://") {
+        Some((scheme, rest)) => {
+            let scheme = match scheme.to_lowercase().as_str() {
+                "http" => "hxxp".to_string(),
+                "https" => "hxxps".to_string(),
+                "ftp" => "fxp".to_string(),
+                "ftps" => "fxps".to_string(),
+                _ => scheme.to_string(),
+            };
+            (Some(scheme), rest)
+        }
+        None => (None, value),
+    };
+
+    // IPv6 addresses (with or without a CIDR suffix) have their colons escaped as well
+    let is_ipv6 = rest
+        .split('/')
+        .next()
+        .is_some_and(|ip| ip.parse::<Ipv6Addr>().is_ok());
+
+    let mut defanged = String::with_capacity(value.len() * 2);
+    if let Some(scheme) = scheme {
+        defanged.push_str(&scheme);
+        defanged.push_str(&style.wrap("://"));
+    }
+    for c in rest.chars() {
+        match c {
+            '.' => defanged.push_str(&style.dot()),
+            '@' => defanged.push_str(&style.at()),
+            ':' if is_ipv6 => defanged.push_str(&style.wrap(":")),
+            _ => defanged.push(c),
+        }
+    }
+    defanged
+}
+
+pub fn refang(value: &str) -> Option<String> {
+    //! Converts a defanged indicator (eg: `hxxp://evil[.]com`, `1.2.3[.]4`,
+    //! `user[@]domain(.)com`) back to its original form.
+    //! Returns None if the given value was not defanged.
+    //!
+    //! ## Example Usage
+    //! ```rust
+    //! use ioc_extract::refang;
+    //!
+    //! assert_eq!(refang("hxxps[:]//evil[.]com").unwrap(), "https://evil.com");
+    //! assert_eq!(refang("evil.com"), None);
+    //! ```
+    if !value.contains(['[', '(', '{']) && !value.to_lowercase().contains("xp") {
+        return None;
+    }
+
+    let refanged = SCHEME.replace(value, |caps: &fancy_regex::Captures| match caps.get(2) {
+        Some(_) => format!("ftp{}", caps.get(3).map_or("", |m| m.as_str())),
+        None => format!("http{}", caps.get(1).map_or("", |m| m.as_str())),
+    });
+    let refanged = SCHEME_SEP.replace_all(&refanged, |caps: &fancy_regex::Captures| {
+        if caps.get(1).is_some() || caps.get(2).is_some() {
+            "://"
+        } else {
+            ":"
+        }
+    });
+    let refanged = DOT.replace_all(&refanged, ".");
+    let refanged = AT.replace_all(&refanged, "@");
+    let refanged = SLASH.replace_all(&refanged, "/");
+
+    if refanged == value {
+        None
+    } else {
+        Some(refanged.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refang() {
+        assert_eq!(refang("hxxp://evil[.]com").unwrap(), "http://evil.com");
+        assert_eq!(refang("hXXps://evil.com").unwrap(), "https://evil.com");
+        assert_eq!(refang("hxxp[:]//evil.com").unwrap(), "http://evil.com");
+        assert_eq!(refang("http[://]evil.com").unwrap(), "http://evil.com");
+        assert_eq!(
+            refang("fxp://files(.)evil(.)com").unwrap(),
+            "ftp://files.evil.com"
+        );
+        assert_eq!(refang("1.2.3[.]4").unwrap(), "1.2.3.4");
+        assert_eq!(refang("evil{.}com").unwrap(), "evil.com");
+        assert_eq!(refang("evil[dot]com").unwrap(), "evil.com");
+        assert_eq!(refang("user[@]domain(.)com").unwrap(), "user@domain.com");
+        assert_eq!(refang("user[at]domain[DOT]com").unwrap(), "user@domain.com");
+        assert_eq!(
+            refang("hxxp://evil[.]com[/]path").unwrap(),
+            "http://evil.com/path"
+        );
+
+        // not defanged
+        assert_eq!(refang("evil.com"), None);
+        assert_eq!(refang("http://evil.com"), None);
+        assert_eq!(refang("hxxpd"), None);
+        assert_eq!(refang("(.*)"), None);
+    }
+
+    #[test]
+    fn test_defang() {
+        assert_eq!(
+            defang("http://evil.com/gate.php", DefangStyle::Brackets),
+            "hxxp[://]evil[.]com/gate[.]php"
+        );
+        assert_eq!(
+            defang("ftp://evil.com", DefangStyle::Parens),
+            "fxp(://)evil(.)com"
+        );
+        assert_eq!(defang("evil.com", DefangStyle::Braces), "evil{.}com");
+        assert_eq!(
+            defang("user@evil.com", DefangStyle::Brackets),
+            "user[@]evil[.]com"
+        );
+        assert_eq!(
+            defang("10.0.0.1", DefangStyle::Words),
+            "10[dot]0[dot]0[dot]1"
+        );
+        assert_eq!(
+            defang("2001:db8::1", DefangStyle::Brackets),
+            "2001[:]db8[:][:]1"
+        );
+        assert_eq!(
+            defang("2001:db8::/32", DefangStyle::Brackets),
+            "2001[:]db8[:][:]/32"
+        );
+    }
+
+    #[test]
+    fn test_defang_refang_round_trip() {
+        let values = [
+            "http://evil.com/gate.php?id=1",
+            "https://sub.evil.co.uk:8443/",
+            "ftp://files.evil.com",
+            "user@evil.com",
+            "10.0.0.1",
+            "evil.com",
+        ];
+        let styles = [
+            DefangStyle::Brackets,
+            DefangStyle::Parens,
+            DefangStyle::Braces,
+            DefangStyle::Words,
+        ];
+        // something that looks like a link to a chat client or ticketing system
+        let live = Regex::new(r"(?i)[a-z]+://|www\.|@[a-z0-9-]+\.|[a-z0-9-]\.[a-z0-9-]").unwrap();
+        for value in values {
+            for style in styles {
+                let defanged = defang(value, style);
+                assert!(!live.is_match(&defanged).unwrap(), "{}", defanged);
+                assert_eq!(refang(&defanged).unwrap(), value);
+            }
+        }
+    }
+}
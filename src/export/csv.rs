@@ -0,0 +1,285 @@
+//! Flat CSV & TSV files of extracted artifacts, with a `type,value,context` layout,
+//! and the matching importer.
+//!
+//! The type is the name of the category (eg: `ip_address`) and the value is as kept
+//! in `Artifacts`; the context is the text around the match, when written from `Matches`.
+//! Fields with a delimiter, a quote or a line break are quoted.
+//!
+//! ## Example Usage
+//! ```rust
+//! use ioc_extract::{export::csv::Csv, Matches};
+//!
+//! let matches = Matches::from_str("beacon to 10.0.0.1 every hour", 10).unwrap();
+//! let mut file = vec![];
+//! Csv::new().write_matches(&mut file, &matches).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(file.clone()).unwrap(),
+//!     "type,value,context\nip_address,10.0.0.1,beacon to 10.0.0.1 every hou\n"
+//! );
+//!
+//! let ioc = Csv::new().read_artifacts(&file[..]).unwrap().unwrap();
+//! assert_eq!(ioc.ip_address.unwrap(), vec!["10.0.0.1"]);
+//! ```
+use crate::{indicator::Category, Artifacts, Matches};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Error, ErrorKind, Read, Result, Write},
+};
+
+/// A row of a CSV/TSV file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub category: Category,
+    pub value: String,
+    pub context: String,
+}
+
+/// Writes & reads CSV or TSV files of artifacts
+#[derive(Debug, Clone)]
+pub struct Csv {
+    delimiter: char,
+    header: bool,
+}
+
+impl Default for Csv {
+    fn default() -> Self {
+        Csv {
+            delimiter: ',',
+            header: true,
+        }
+    }
+}
+
+impl Csv {
+    pub fn new() -> Self {
+        //! Comma separated, with a header row
+        Self::default()
+    }
+
+    pub fn tsv() -> Self {
+        //! Tab separated, with a header row
+        Csv {
+            delimiter: '\t',
+            ..Self::default()
+        }
+    }
+
+    pub fn header(mut self, header: bool) -> Self {
+        //! Write a `type,value,context` header row; when reading, a header row is always skipped
+        self.header = header;
+        self
+    }
+
+    fn field(&self, value: &str) -> String {
+        //! Quotes a field if needed (RFC 4180)
+        if value.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    pub fn row(&self, fields: &[&str]) -> String {
+        //! A row of the given fields, quoted if needed & without the line ending
+        fields
+            .iter()
+            .map(|x| self.field(x))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter.to_string())
+    }
+
+    pub fn write_records<'a, W, I>(&self, mut writer: W, records: I) -> Result<()>
+    where
+        W: Write,
+        I: IntoIterator<Item = (Category, &'a str, &'a str)>,
+    {
+        //! Writes rows of (type, value, context)
+        if self.header {
+            writeln!(writer, "{}", self.row(&["type", "value", "context"]))?;
+        }
+        for (category, value, context) in records {
+            writeln!(writer, "{}", self.row(&[category.name(), value, context]))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_artifacts<W: Write>(&self, writer: W, artifacts: &Artifacts) -> Result<()> {
+        //! Writes all the artifacts, with an empty context
+        let records = Category::all().into_iter().flat_map(|category| {
+            artifacts
+                .get(category)
+                .into_iter()
+                .flatten()
+                .map(move |value| (category, value.as_str(), ""))
+        });
+        self.write_records(writer, records)
+    }
+
+    pub fn write_matches<W: Write>(&self, writer: W, matches: &Matches) -> Result<()> {
+        //! Writes every match, along with its context
+        let records = Category::all().into_iter().flat_map(|category| {
+            matches
+                .get(category)
+                .into_iter()
+                .flatten()
+                .map(move |m| (category, m.value.as_str(), m.context.as_str()))
+        });
+        self.write_records(writer, records)
+    }
+
+    pub fn read<R: Read>(&self, mut reader: R) -> Result<Vec<Record>> {
+        //! Reads the rows of a file written by this writer (or any `type,value[,context]` file)
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let mut records = vec![];
+        for (line, row) in self.rows(&text)?.into_iter().enumerate() {
+            if row.iter().all(|field| field.is_empty()) {
+                continue;
+            }
+            if line == 0 && row.first().is_some_and(|x| x == "type") {
+                continue;
+            }
+            let invalid = |message: String| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("row {}: {}", line + 1, message),
+                )
+            };
+            let category = Category::all()
+                .into_iter()
+                .find(|c| c.name() == row[0])
+                .ok_or_else(|| invalid(format!("unknown type '{}'", row[0])))?;
+            let value = row.get(1).ok_or_else(|| invalid("no value".to_string()))?;
+            records.push(Record {
+                category,
+                value: value.to_string(),
+                context: row.get(2).cloned().unwrap_or_default(),
+            });
+        }
+        Ok(records)
+    }
+
+    pub fn read_artifacts<R: Read>(&self, reader: R) -> Result<Option<Artifacts>> {
+        //! Reads a file back into (sorted & de-duplicated) Artifacts
+        let mut found: BTreeMap<Category, BTreeSet<String>> = BTreeMap::new();
+        for record in self.read(reader)? {
+            found
+                .entry(record.category)
+                .or_default()
+                .insert(record.value);
+        }
+        if found.is_empty() {
+            return Ok(None);
+        }
+        let mut artifacts = Artifacts::default();
+        for (category, values) in found {
+            *artifacts.get_mut(category) = Some(values.into_iter().collect());
+        }
+        Ok(Some(artifacts))
+    }
+
+    fn rows(&self, text: &str) -> Result<Vec<Vec<String>>> {
+        //! Splits the text into rows of fields; quoted fields may have line breaks
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                }
+                '"' if field.is_empty() => quoted = true,
+                c if quoted => field.push(c),
+                c if c == self.delimiter => row.push(std::mem::take(&mut field)),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                c => field.push(c),
+            }
+        }
+        if quoted {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "unterminated quoted field",
+            ));
+        }
+        if !field.is_empty() || !row.is_empty() {
+            row.push(field);
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "see https://www.example.com from 10.0.0.1\nSELECT a, \"b\" FROM x\nd41d8cd98f00b204e9800998ecf8427e";
+
+    #[test]
+    fn test_round_trip() {
+        let ioc = Artifacts::from_str(TEXT).unwrap();
+        for csv in [Csv::new(), Csv::tsv(), Csv::new().header(false)] {
+            let mut file = vec![];
+            csv.write_artifacts(&mut file, &ioc).unwrap();
+            let read = csv.read_artifacts(&file[..]).unwrap().unwrap();
+            assert_eq!(
+                serde_json::to_value(&read).unwrap(),
+                serde_json::to_value(&ioc).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_row() {
+        let csv = Csv::new();
+        assert_eq!(csv.row(&["urls", "10.0.0.1"]), "urls,10.0.0.1");
+        assert_eq!(csv.row(&["SELECT a, b"]), "\"SELECT a, b\"");
+        assert_eq!(csv.row(&["say \"hi\""]), "\"say \"\"hi\"\"\"");
+        assert_eq!(Csv::tsv().row(&["a, b", "c"]), "a, b\tc");
+    }
+
+    #[test]
+    fn test_matches() {
+        let matches = Matches::from_str(TEXT, 5).unwrap();
+        let mut file = vec![];
+        Csv::new().write_matches(&mut file, &matches).unwrap();
+        let text = String::from_utf8(file.clone()).unwrap();
+        assert!(text.contains("\nsql,\"SELECT a, \"\"b\"\" FROM x\",\".0.1\nSELECT a,"));
+
+        let records = Csv::new().read(&file[..]).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(
+            records[0],
+            Record {
+                category: Category::Urls,
+                value: "https://www.example.com".to_string(),
+                context: "see https://www.example.com from".to_string()
+            }
+        );
+        let sql = &matches.sql.unwrap()[0];
+        assert_eq!(records[3].value, sql.value);
+        assert_eq!(records[3].context, sql.context);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let err = Csv::new()
+            .read(&b"type,value\nips,10.0.0.1\n"[..])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "row 2: unknown type 'ips'");
+        assert!(Csv::new().read(&b"sql,\"SELECT\n"[..]).is_err());
+        assert!(Csv::new().read(&b"\n\n"[..]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+//! MISP event JSON documents of extracted artifacts, to import a whole report into a
+//! MISP instance (eg: `POST /events/add` or Add Event > Import from MISP JSON).
+//!
+//! Each indicator becomes an attribute with the MISP type & category it maps to, and
+//! the `to_ids` default of that type. The UUIDs are UUIDv5 of the description & the
+//! attributes, so importing the same report again updates the event rather than
+//! duplicating it, while reports with the same description stay apart.
+//!
+//! ## Example Usage
+//! ```rust
+//! use ioc_extract::{export::misp::Misp, Extractor};
+//!
+//! let text = "beacon to 10.0.0.1 or https://evil-domain.com/a.php";
+//! let matches = Extractor::new().matches(text).unwrap();
+//! let event = Misp::new("Phishing campaign report")
+//!     .tag("tlp:amber")
+//!     .event(matches.unique_indicators());
+//! println!("{}", serde_json::to_string_pretty(&event).unwrap());
+//! ```
+use super::{timestamp, uuid};
+use crate::{Artifacts, Indicator, Matches};
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A MISP attribute; its type, category & whether it is for detection
+struct Attribute {
+    kind: &'static str,
+    category: &'static str,
+    to_ids: bool,
+    value: String,
+    comment: String,
+}
+
+fn attribute(indicator: &Indicator) -> Option<Attribute> {
+    let network = "Network activity";
+    let payload = "Payload delivery";
+    let external = "External analysis";
+    let mut comment = String::new();
+    let (kind, category, to_ids) = match indicator {
+        Indicator::Url { .. } => ("url", network, true),
+        Indicator::Domain { .. } => ("domain", network, true),
+        Indicator::Email { .. } => ("email-src", payload, true),
+        Indicator::Ip { .. } => ("ip-dst", network, true),
+        Indicator::Endpoint {
+            version: Some(_), ..
+        } => ("ip-dst|port", network, true),
+        Indicator::Endpoint { version: None, .. } => ("hostname|port", network, true),
+        Indicator::Crypto { coin, .. } => match coin.as_str() {
+            "Bitcoin" => ("btc", "Financial fraud", true),
+            "Monero" => ("xmr", "Financial fraud", true),
+            "Dash" => ("dash", "Financial fraud", true),
+            _ => {
+                comment = format!("{} address", coin);
+                ("text", "Financial fraud", false)
+            }
+        },
+        Indicator::CryptoSecret { kind, .. } => {
+            comment = kind.to_string();
+            ("text", "Financial fraud", false)
+        }
+        Indicator::Block {
+            certificate: Some(certificate),
+            ..
+        } => {
+            comment = certificate.subject.to_string();
+            ("x509-fingerprint-sha256", network, true)
+        }
+        Indicator::Block { kind, .. } => {
+            comment = kind.to_string();
+            ("text", "Other", false)
+        }
+        Indicator::Hash { algorithm, .. } => match algorithm.as_str() {
+            "MD5" => ("md5", payload, true),
+            "SHA-1" => ("sha1", payload, true),
+            "SHA-256" => ("sha256", payload, true),
+            "SHA-512" => ("sha512", payload, true),
+            "SSDEEP" => ("ssdeep", payload, true),
+            "TLSH" => ("tlsh", payload, true),
+            "Imphash" => ("imphash", payload, true),
+            _ => return None,
+        },
+        Indicator::Vulnerability { kind, .. } => match kind.as_str() {
+            "CVE" | "GHSA" => ("vulnerability", external, false),
+            "CWE" => ("weakness", external, false),
+            _ => {
+                comment = kind.to_string();
+                ("text", external, false)
+            }
+        },
+        Indicator::AttackId { kind, .. } => {
+            comment = format!("MITRE ATT&CK {}", kind);
+            ("text", external, false)
+        }
+        Indicator::RegistryKey { .. } => ("regkey", "Persistence mechanism", true),
+        Indicator::FilePath { .. } => ("filename", payload, true),
+        // credentials are not shared
+        Indicator::Secret { .. } | Indicator::Sql { .. } | Indicator::Regex { .. } => return None,
+    };
+    let value = match indicator {
+        Indicator::Ip {
+            value,
+            cidr: Some(cidr),
+            ..
+        } => format!("{}/{}", value, cidr),
+        // composite attributes are separated by a pipe
+        Indicator::Endpoint { host, port, .. } => format!("{}|{}", host, port),
+        Indicator::Block {
+            certificate: Some(certificate),
+            ..
+        } => certificate.sha256.to_string(),
+        _ => indicator.value().to_string(),
+    };
+    Some(Attribute {
+        kind,
+        category,
+        to_ids,
+        value,
+        comment,
+    })
+}
+
+/// Builds MISP events from `Artifacts`
+#[derive(Debug, Clone)]
+pub struct Misp {
+    info: String,
+    time: Option<SystemTime>,
+    tags: Vec<String>,
+}
+
+impl Misp {
+    pub fn new(info: &str) -> Self {
+        //! An event with the given description (the `info` of the event), dated now
+        Misp {
+            info: info.to_string(),
+            time: None,
+            tags: vec![],
+        }
+    }
+
+    pub fn time(mut self, time: SystemTime) -> Self {
+        //! Date & timestamp of the event; defaults to the time of the export
+        self.time = Some(time);
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        //! Adds a tag to the event, eg: `tlp:amber`
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn event<I>(&self, indicators: I) -> Value
+    where
+        I: IntoIterator<Item = Indicator>,
+    {
+        //! A MISP event of the given indicators
+        let time = self.time.unwrap_or_else(SystemTime::now);
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .to_string();
+        let attributes: Vec<Attribute> = indicators
+            .into_iter()
+            .filter_map(|indicator| attribute(&indicator))
+            .collect();
+        let mut values: Vec<String> = attributes
+            .iter()
+            .map(|attribute| format!("{} {}", attribute.kind, attribute.value))
+            .collect();
+        values.sort();
+        let event_uuid = uuid(&format!("{}\n{}", self.info, values.join("\n")));
+
+        let attributes: Vec<Value> = attributes
+            .into_iter()
+            .map(|attribute| {
+                json!({
+                    "uuid": uuid(&format!("{} {} {}", event_uuid, attribute.kind, attribute.value)),
+                    "type": attribute.kind,
+                    "category": attribute.category,
+                    "value": attribute.value,
+                    "to_ids": attribute.to_ids,
+                    "comment": attribute.comment,
+                    "distribution": "5",
+                    "timestamp": secs,
+                })
+            })
+            .collect();
+        let tags: Vec<Value> = self.tags.iter().map(|tag| json!({ "name": tag })).collect();
+
+        json!({
+            "Event": {
+                "uuid": event_uuid,
+                "info": self.info,
+                "date": &timestamp(time)[..10],
+                "timestamp": secs,
+                "threat_level_id": "4",
+                "analysis": "0",
+                "distribution": "0",
+                "published": false,
+                "Attribute": attributes,
+                "Tag": tags,
+            }
+        })
+    }
+}
+
+impl Matches {
+    pub fn to_misp(&self, info: &str) -> Value {
+        //! A MISP event of the found indicators, with the given description
+        Misp::new(info).event(self.unique_indicators())
+    }
+}
+
+impl Artifacts {
+    pub fn to_misp(&self, info: &str) -> Value {
+        //! A MISP event of the artifacts, with the given description
+        Misp::new(info).event(self.indicators())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(event: &Value) -> Vec<(String, String, bool)> {
+        event["Event"]["Attribute"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|x| {
+                (
+                    x["type"].as_str().unwrap().to_string(),
+                    x["value"].as_str().unwrap().to_string(),
+                    x["to_ids"].as_bool().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_event() {
+        let text = "pay 1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9 from johndoe@example.com to 10.0.0.0/8 via [2001:db8::1]:443\nHKLM\\Software\\Evil\nCVE-2021-44228 T1059";
+        let ioc = Artifacts::from_str(text).unwrap();
+        let event = Misp::new("report").tag("tlp:amber").event(ioc.indicators());
+        let found = attributes(&event);
+        for attribute in [
+            ("email-src", "johndoe@example.com", true),
+            ("ip-dst", "10.0.0.0/8", true),
+            ("ip-dst|port", "2001:db8::1|443", true),
+            ("btc", "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9", true),
+            ("vulnerability", "CVE-2021-44228", false),
+            ("text", "T1059", false),
+            ("regkey", "HKLM\\Software\\Evil", true),
+        ] {
+            let attribute = (
+                attribute.0.to_string(),
+                attribute.1.to_string(),
+                attribute.2,
+            );
+            assert!(found.contains(&attribute), "{:?}", attribute);
+        }
+        assert_eq!(event["Event"]["Tag"][0]["name"], "tlp:amber");
+        assert_eq!(event["Event"]["info"], "report");
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let ioc = Artifacts::from_str("beacon to 10.0.0.1").unwrap();
+        let misp = Misp::new("report").time(UNIX_EPOCH);
+        let event = misp.event(ioc.indicators());
+        assert_eq!(event, misp.event(ioc.indicators()));
+        assert_eq!(event["Event"]["date"], "1970-01-01");
+        assert_ne!(
+            event["Event"]["uuid"],
+            Misp::new("another report").event(ioc.indicators())["Event"]["uuid"]
+        );
+        assert_ne!(
+            event["Event"]["uuid"],
+            misp.event(
+                Artifacts::from_str("beacon to 10.0.0.2")
+                    .unwrap()
+                    .indicators()
+            )["Event"]["uuid"],
+            "reports with the same description do not collide"
+        );
+        let matches = Matches::from_str("beacon to 10.0.0.1", 0).unwrap();
+        assert_eq!(misp.event(matches.unique_indicators()), event);
+    }
+}
@@ -0,0 +1,64 @@
+//! Exports of `Artifacts` to the formats of threat intelligence platforms
+pub mod csv;
+pub mod misp;
+pub mod openioc;
+pub mod stix;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+fn uuid(name: &str) -> String {
+    //! A UUIDv5 in the namespace of this crate, so exports of the same artifacts are the same
+    let namespace = Uuid::new_v5(
+        &Uuid::NAMESPACE_URL,
+        b"https://github.com/marirs/ioc_extract-rs",
+    );
+    Uuid::new_v5(&namespace, name.as_bytes()).to_string()
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    //! The (year, month, day) of a number of days since 1970-01-01
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+fn timestamp(time: SystemTime) -> String {
+    //! RFC 3339 timestamp in UTC, with milliseconds (eg: 2021-12-10T08:17:27.000Z)
+    let since = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since.as_secs() as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let secs = secs.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        since.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            timestamp(UNIX_EPOCH + Duration::from_millis(1_709_251_199_123)),
+            "2024-02-29T23:59:59.123Z"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! OpenIOC 1.1 XML documents of extracted artifacts.
+//!
+//! Each indicator becomes an `IndicatorItem` with the search term of its category
+//! (eg: `Network/DNS`, `PortItem/remoteIP`, `RegistryItem/Path`, `FileItem/FullPath`),
+//! all under one `OR` Indicator. Categories with no search term (SQL, Regexes, ...) are left out.
+//!
+//! ## Example Usage
+//! ```rust
+//! use ioc_extract::{export::openioc::OpenIoc, Extractor};
+//!
+//! let matches = Extractor::new().matches("beacon to 10.0.0.1 or www.evil-domain.com").unwrap();
+//! let xml = OpenIoc::new("Phishing campaign report").document(matches.unique_indicators());
+//! println!("{}", xml);
+//! ```
+use super::{timestamp, uuid};
+use crate::{Artifacts, Indicator, Matches};
+use std::{fmt::Write, time::SystemTime};
+
+/// The search term of an IndicatorItem; document, search & the type of the content
+struct Term {
+    document: &'static str,
+    search: &'static str,
+    content: &'static str,
+}
+
+fn term(indicator: &Indicator) -> Option<Term> {
+    let (document, search, content) = match indicator {
+        Indicator::Url { .. } => ("Network", "Network/URI", "string"),
+        Indicator::Domain { .. } => ("Network", "Network/DNS", "string"),
+        Indicator::Email { .. } => ("Email", "Email/From", "string"),
+        Indicator::Ip { .. } => ("PortItem", "PortItem/remoteIP", "IP"),
+        Indicator::Hash { algorithm, .. } => match algorithm.as_str() {
+            "MD5" => ("FileItem", "FileItem/Md5sum", "md5"),
+            "SHA-1" => ("FileItem", "FileItem/Sha1sum", "sha1"),
+            "SHA-256" => ("FileItem", "FileItem/Sha256sum", "sha256"),
+            "Imphash" => ("FileItem", "FileItem/PEInfo/ImpHash", "string"),
+            _ => return None,
+        },
+        Indicator::RegistryKey { .. } => ("RegistryItem", "RegistryItem/Path", "string"),
+        Indicator::FilePath { .. } => ("FileItem", "FileItem/FullPath", "string"),
+        _ => return None,
+    };
+    Some(Term {
+        document,
+        search,
+        content,
+    })
+}
+
+fn escape(value: &str) -> String {
+    //! Escapes a string for XML text & attributes
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Builds OpenIOC 1.1 documents from `Artifacts`
+#[derive(Debug, Clone)]
+pub struct OpenIoc {
+    description: String,
+    author: String,
+    time: Option<SystemTime>,
+}
+
+impl OpenIoc {
+    pub fn new(description: &str) -> Self {
+        //! A document with the given short description, dated now
+        OpenIoc {
+            description: description.to_string(),
+            author: "ioc_extract".to_string(),
+            time: None,
+        }
+    }
+
+    pub fn author(mut self, author: &str) -> Self {
+        //! Author of the document; defaults to ioc_extract
+        self.author = author.to_string();
+        self
+    }
+
+    pub fn time(mut self, time: SystemTime) -> Self {
+        //! Date of the document; defaults to the time of the export
+        self.time = Some(time);
+        self
+    }
+
+    pub fn document<I>(&self, indicators: I) -> String
+    where
+        I: IntoIterator<Item = Indicator>,
+    {
+        //! An OpenIOC 1.1 XML document of the given indicators
+        let time = timestamp(self.time.unwrap_or_else(SystemTime::now));
+        // OpenIOC dates have no fractional seconds nor time zone
+        let time = &time[..19];
+        let id = uuid(&self.description);
+
+        let mut xml = String::new();
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            xml,
+            r#"<OpenIOC xmlns="http://openioc.org/schemas/OpenIOC_1.1" id="{}" last-modified="{}" published-date="{}">"#,
+            id, time, time
+        );
+        let _ = writeln!(xml, "  <metadata>");
+        let _ = writeln!(
+            xml,
+            "    <short_description>{}</short_description>",
+            escape(&self.description)
+        );
+        let _ = writeln!(
+            xml,
+            "    <authored_by>{}</authored_by>",
+            escape(&self.author)
+        );
+        let _ = writeln!(xml, "    <authored_date>{}</authored_date>", time);
+        let _ = writeln!(xml, "    <links/>");
+        let _ = writeln!(xml, "  </metadata>");
+        let _ = writeln!(xml, "  <criteria>");
+        let _ = writeln!(
+            xml,
+            r#"    <Indicator id="{}" operator="OR">"#,
+            uuid(&format!("{} indicator", id))
+        );
+        for indicator in indicators {
+            let term = match term(&indicator) {
+                Some(term) => term,
+                None => continue,
+            };
+            let value = match &indicator {
+                Indicator::Ip {
+                    value,
+                    cidr: Some(cidr),
+                    ..
+                } => format!("{}/{}", value, cidr),
+                _ => indicator.value().to_string(),
+            };
+            let _ = writeln!(
+                xml,
+                r#"      <IndicatorItem id="{}" condition="is" preserve-case="false" negate="false">"#,
+                uuid(&format!("{} {} {}", id, term.search, value))
+            );
+            let _ = writeln!(
+                xml,
+                r#"        <Context document="{}" search="{}" type="mir"/>"#,
+                term.document, term.search
+            );
+            let _ = writeln!(
+                xml,
+                r#"        <Content type="{}">{}</Content>"#,
+                term.content,
+                escape(&value)
+            );
+            let _ = writeln!(xml, "      </IndicatorItem>");
+        }
+        let _ = writeln!(xml, "    </Indicator>");
+        let _ = writeln!(xml, "  </criteria>");
+        let _ = writeln!(xml, "  <parameters/>");
+        let _ = writeln!(xml, "</OpenIOC>");
+        xml
+    }
+}
+
+impl Matches {
+    pub fn to_openioc(&self, description: &str) -> String {
+        //! An OpenIOC 1.1 XML document of the found indicators, with the given short
+        //! description
+        OpenIoc::new(description).document(self.unique_indicators())
+    }
+}
+
+impl Artifacts {
+    pub fn to_openioc(&self, description: &str) -> String {
+        //! An OpenIOC 1.1 XML document of the artifacts, with the given short description
+        OpenIoc::new(description).document(self.indicators())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_document() {
+        let text = "beacon to 10.0.0.1 or www.evil-domain.com\nHKLM\\Software\\<Evil>\nd41d8cd98f00b204e9800998ecf8427e SELECT * FROM users";
+        let ioc = Artifacts::from_str(text).unwrap();
+        let xml = OpenIoc::new("report & more")
+            .time(UNIX_EPOCH)
+            .document(ioc.indicators());
+        for expected in [
+            r#"published-date="1970-01-01T00:00:00""#,
+            "<short_description>report &amp; more</short_description>",
+            r#"<Context document="Network" search="Network/DNS" type="mir"/>"#,
+            r#"<Content type="string">www.evil-domain.com</Content>"#,
+            r#"<Context document="PortItem" search="PortItem/remoteIP" type="mir"/>"#,
+            r#"<Content type="IP">10.0.0.1</Content>"#,
+            r#"<Content type="md5">d41d8cd98f00b204e9800998ecf8427e</Content>"#,
+            r#"<Context document="RegistryItem" search="RegistryItem/Path" type="mir"/>"#,
+            r#"<Content type="string">HKLM\Software\&lt;Evil&gt;</Content>"#,
+        ] {
+            assert!(xml.contains(expected), "{}", expected);
+        }
+        assert!(!xml.contains("SELECT"));
+        assert_eq!(xml.matches("<IndicatorItem ").count(), 4);
+        let matches = Matches::from_str(text, 0).unwrap();
+        assert_eq!(
+            OpenIoc::new("report & more")
+                .time(UNIX_EPOCH)
+                .document(matches.unique_indicators()),
+            xml
+        );
+    }
+}
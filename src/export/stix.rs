@@ -0,0 +1,403 @@
+//! STIX 2.1 bundles of extracted artifacts, for OpenCTI-style stores.
+//!
+//! Every indicator becomes an `indicator` object with a STIX pattern, and/or the
+//! Cyber-observable Object (SCO) it is based on; CVE & GHSA become `vulnerability`
+//! objects and CAPEC `attack-pattern` objects, while CWE, a weakness, is not exported.
+//! The identifiers are UUIDv5, so exporting the same artifacts again gives the same
+//! objects; SCOs use the deterministic identifiers of the STIX specification.
+//!
+//! ## Example Usage
+//! ```rust
+//! use ioc_extract::{export::stix::Stix, Extractor};
+//!
+//! let text = "beacon to 10.0.0.1 or https://evil-domain.com/a.php";
+//! let matches = Extractor::new().matches(text).unwrap();
+//! let bundle = Stix::new().bundle(matches.unique_indicators());
+//! println!("{}", serde_json::to_string_pretty(&bundle).unwrap());
+//! ```
+use super::timestamp;
+use crate::{Artifacts, Indicator, IpVersion, Matches};
+use serde_json::{json, Map, Value};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Namespace of the deterministic identifiers of STIX Cyber-observable Objects
+const NAMESPACE: Uuid = Uuid::from_u128(0x00abedb4_aa42_466c_9c01_fed23315a9b7);
+
+/// The Cyber-observable Object an indicator is about
+struct Observable {
+    /// Type of the SCO, eg: ipv4-addr
+    kind: &'static str,
+    /// Property of the SCO the pattern compares, eg: value or hashes.'SHA-256'
+    property: String,
+    value: String,
+    /// Properties of the SCO that make up its identifier; `None` when only a pattern can be
+    /// built, eg: an imphash, as the PE type the SCO requires is unknown
+    properties: Option<Value>,
+}
+
+fn observable(indicator: &Indicator) -> Option<Observable> {
+    let value = indicator.value().to_string();
+    let (kind, property, properties) = match indicator {
+        Indicator::Url { .. } => ("url", "value".to_string(), json!({ "value": value })),
+        Indicator::Domain { .. } => (
+            "domain-name",
+            "value".to_string(),
+            json!({ "value": value }),
+        ),
+        Indicator::Email { .. } => ("email-addr", "value".to_string(), json!({ "value": value })),
+        Indicator::Ip { version, cidr, .. } => {
+            let kind = match version {
+                IpVersion::V4 => "ipv4-addr",
+                IpVersion::V6 => "ipv6-addr",
+            };
+            let value = match cidr {
+                Some(cidr) => format!("{}/{}", value, cidr),
+                None => value,
+            };
+            return Some(Observable {
+                kind,
+                property: "value".to_string(),
+                properties: Some(json!({ "value": value })),
+                value,
+            });
+        }
+        Indicator::Hash { algorithm, .. } => match algorithm.as_str() {
+            "MD5" | "SHA-1" | "SHA-256" | "SHA-512" | "SSDEEP" | "TLSH" => (
+                "file",
+                format!("hashes.'{}'", algorithm),
+                json!({ "hashes": { algorithm.as_str(): value } }),
+            ),
+            "Imphash" => {
+                return Some(Observable {
+                    kind: "file",
+                    property: "extensions.'windows-pebinary-ext'.imphash".to_string(),
+                    value,
+                    properties: None,
+                })
+            }
+            _ => return None,
+        },
+        // certificates by their fingerprint, as their PEM is not a property of the SCO
+        Indicator::Block {
+            certificate: Some(certificate),
+            ..
+        } => {
+            return Some(Observable {
+                kind: "x509-certificate",
+                property: "hashes.'SHA-256'".to_string(),
+                properties: Some(json!({ "hashes": { "SHA-256": certificate.sha256 } })),
+                value: certificate.sha256.to_string(),
+            });
+        }
+        Indicator::RegistryKey { .. } => (
+            "windows-registry-key",
+            "key".to_string(),
+            json!({ "key": value }),
+        ),
+        _ => return None,
+    };
+    Some(Observable {
+        kind,
+        property,
+        value,
+        properties: Some(properties),
+    })
+}
+
+fn escape(value: &str) -> String {
+    //! Escapes a string for a STIX pattern
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn id(kind: &str, name: &str) -> String {
+    format!("{}--{}", kind, Uuid::new_v5(&NAMESPACE, name.as_bytes()))
+}
+
+/// Builds STIX 2.1 bundles from `Artifacts`
+#[derive(Debug, Clone)]
+pub struct Stix {
+    time: Option<SystemTime>,
+    indicators: bool,
+    observables: bool,
+}
+
+impl Default for Stix {
+    fn default() -> Self {
+        Stix {
+            time: None,
+            indicators: true,
+            observables: true,
+        }
+    }
+}
+
+impl Stix {
+    pub fn new() -> Self {
+        //! Exports both indicator objects & the SCOs they are based on, timestamped now
+        Self::default()
+    }
+
+    pub fn time(mut self, time: SystemTime) -> Self {
+        //! Time used for `created`, `modified` & `valid_from`; defaults to the time of the export
+        self.time = Some(time);
+        self
+    }
+
+    pub fn indicators(mut self, indicators: bool) -> Self {
+        //! Export `indicator` objects with STIX patterns
+        self.indicators = indicators;
+        self
+    }
+
+    pub fn observables(mut self, observables: bool) -> Self {
+        //! Export the Cyber-observable Objects; along with `indicators`, each is linked to
+        //! its indicator with a `based-on` relationship
+        self.observables = observables;
+        self
+    }
+
+    pub fn objects<I>(&self, indicators: I) -> Vec<Value>
+    where
+        I: IntoIterator<Item = Indicator>,
+    {
+        //! The STIX objects of the given indicators, without duplicates
+        let time = timestamp(self.time.unwrap_or_else(SystemTime::now));
+        let mut objects: Map<String, Value> = Map::new();
+        let mut add = |object: Value| {
+            objects.insert(
+                object["id"].as_str().unwrap_or_default().to_string(),
+                object,
+            );
+        };
+
+        for indicator in indicators {
+            if let Indicator::Vulnerability { value, kind } = &indicator {
+                let (sdo, source) = match kind.as_str() {
+                    "CVE" => ("vulnerability", "cve"),
+                    "GHSA" => ("vulnerability", "ghsa"),
+                    "CAPEC" => ("attack-pattern", "capec"),
+                    _ => continue,
+                };
+                add(json!({
+                    "type": sdo,
+                    "spec_version": "2.1",
+                    "id": id(sdo, value),
+                    "created": time,
+                    "modified": time,
+                    "name": value,
+                    "external_references": [
+                        { "source_name": source, "external_id": value }
+                    ]
+                }));
+                continue;
+            }
+            let observable = match observable(&indicator) {
+                Some(observable) => observable,
+                None => continue,
+            };
+
+            let sco_id = observable
+                .properties
+                .as_ref()
+                .map(|properties| id(observable.kind, &properties.to_string()));
+            if let (true, Some(sco_id), Some(Value::Object(properties))) =
+                (self.observables, &sco_id, observable.properties)
+            {
+                let mut sco = Map::new();
+                sco.insert("type".to_string(), json!(observable.kind));
+                sco.insert("spec_version".to_string(), json!("2.1"));
+                sco.insert("id".to_string(), json!(sco_id));
+                sco.extend(properties);
+                add(Value::Object(sco));
+            }
+            if self.indicators {
+                let pattern = format!(
+                    "[{}:{} = '{}']",
+                    observable.kind,
+                    observable.property,
+                    escape(&observable.value)
+                );
+                let indicator_id = id("indicator", &pattern);
+                add(json!({
+                    "type": "indicator",
+                    "spec_version": "2.1",
+                    "id": indicator_id,
+                    "created": time,
+                    "modified": time,
+                    "name": observable.value,
+                    "pattern": pattern,
+                    "pattern_type": "stix",
+                    "pattern_version": "2.1",
+                    "valid_from": time,
+                }));
+                if let (true, Some(sco_id)) = (self.observables, &sco_id) {
+                    add(json!({
+                        "type": "relationship",
+                        "spec_version": "2.1",
+                        "id": id("relationship", &format!("based-on {} {}", indicator_id, sco_id)),
+                        "created": time,
+                        "modified": time,
+                        "relationship_type": "based-on",
+                        "source_ref": indicator_id,
+                        "target_ref": sco_id,
+                    }));
+                }
+            }
+        }
+        objects.into_iter().map(|(_, object)| object).collect()
+    }
+
+    pub fn bundle<I>(&self, indicators: I) -> Value
+    where
+        I: IntoIterator<Item = Indicator>,
+    {
+        //! A STIX bundle of the given indicators; its identifier is derived from its objects
+        let objects = self.objects(indicators);
+        let ids: Vec<&str> = objects
+            .iter()
+            .filter_map(|object| object["id"].as_str())
+            .collect();
+        json!({
+            "type": "bundle",
+            "id": id("bundle", &ids.join(",")),
+            "objects": objects,
+        })
+    }
+}
+
+impl Matches {
+    pub fn to_stix(&self) -> Value {
+        //! A STIX 2.1 bundle of the found indicators, with indicators & observables
+        Stix::new().bundle(self.unique_indicators())
+    }
+}
+
+impl Artifacts {
+    pub fn to_stix(&self) -> Value {
+        //! A STIX 2.1 bundle of the artifacts, with indicators & observables
+        Stix::new().bundle(self.indicators())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    const TEXT: &str = "see https://evil-domain.com/a.php on evil-domain.com from 10.0.0.0/8 and 2001:db8::1\nHKLM\\Software\\Evil's\nd41d8cd98f00b204e9800998ecf8427e CVE-2021-44228 SELECT * FROM users\nimphash: f34d5f2d4577ed6d9ceec516c1f5a744 CWE-79 CAPEC-66 GHSA-jfh8-c2jp-5v3q";
+
+    fn patterns(bundle: &Value) -> Vec<&str> {
+        let mut patterns: Vec<&str> = bundle["objects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|object| object["pattern"].as_str())
+            .collect();
+        patterns.sort();
+        patterns
+    }
+
+    #[test]
+    fn test_patterns() {
+        let bundle = Stix::new().bundle(Artifacts::from_str(TEXT).unwrap().indicators());
+        assert_eq!(
+            patterns(&bundle),
+            vec![
+                "[domain-name:value = 'evil-domain.com']",
+                "[file:extensions.'windows-pebinary-ext'.imphash = 'f34d5f2d4577ed6d9ceec516c1f5a744']",
+                "[file:hashes.'MD5' = 'd41d8cd98f00b204e9800998ecf8427e']",
+                "[ipv4-addr:value = '10.0.0.0/8']",
+                "[ipv6-addr:value = '2001:db8::1']",
+                "[url:value = 'https://evil-domain.com/a.php']",
+                "[windows-registry-key:key = 'HKLM\\\\Software\\\\Evil\\'s']",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vulnerabilities() {
+        let bundle = Stix::new().bundle(Artifacts::from_str(TEXT).unwrap().indicators());
+        let mut references: Vec<(&str, &str, &str)> = bundle["objects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|object| {
+                object["type"] == "vulnerability" || object["type"] == "attack-pattern"
+            })
+            .map(|object| {
+                let reference = &object["external_references"][0];
+                (
+                    object["type"].as_str().unwrap(),
+                    reference["source_name"].as_str().unwrap(),
+                    reference["external_id"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        references.sort();
+        assert_eq!(
+            references,
+            vec![
+                ("attack-pattern", "capec", "CAPEC-66"),
+                ("vulnerability", "cve", "CVE-2021-44228"),
+                ("vulnerability", "ghsa", "GHSA-jfh8-c2jp-5v3q"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_imphash() {
+        let ioc = Artifacts::from_str("imphash: f34d5f2d4577ed6d9ceec516c1f5a744").unwrap();
+        let objects = Stix::new().objects(ioc.indicators());
+        assert_eq!(
+            objects.len(),
+            1,
+            "only an indicator, as the PE type is unknown"
+        );
+        assert_eq!(objects[0]["type"], "indicator");
+        assert!(Stix::new()
+            .indicators(false)
+            .objects(ioc.indicators())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let ioc = Artifacts::from_str(TEXT).unwrap();
+        let stix = Stix::new().time(UNIX_EPOCH);
+        let bundle = stix.bundle(ioc.indicators());
+        assert_eq!(bundle, stix.bundle(ioc.indicators()));
+        assert_eq!(
+            bundle["id"],
+            Stix::new().bundle(ioc.indicators())["id"],
+            "the identifiers do not depend on the time"
+        );
+        assert_eq!(
+            stix.bundle(Matches::from_str(TEXT, 0).unwrap().unique_indicators()),
+            bundle,
+            "the typed Indicators of the matches give the same bundle"
+        );
+    }
+
+    #[test]
+    fn test_observables() {
+        let ioc = Artifacts::from_str("beacon to 10.0.0.1").unwrap();
+        let objects = Stix::new().indicators(false).objects(ioc.indicators());
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0]["type"], "ipv4-addr");
+        assert_eq!(objects[0]["value"], "10.0.0.1");
+        assert_eq!(
+            objects[0]["id"],
+            format!(
+                "ipv4-addr--{}",
+                Uuid::new_v5(&NAMESPACE, br#"{"value":"10.0.0.1"}"#)
+            )
+        );
+        assert_eq!(Stix::new().objects(ioc.indicators()).len(), 3);
+        assert!(Stix::new()
+            .indicators(false)
+            .observables(false)
+            .objects(ioc.indicators())
+            .is_empty());
+    }
+}
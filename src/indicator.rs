@@ -0,0 +1,537 @@
+use crate::validators::{block, network, secret};
+use serde::{Deserialize, Serialize};
+
+/// The different categories of artifacts; one per field of `Artifacts`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Urls,
+    Domains,
+    Emails,
+    IpAddress,
+    Endpoints,
+    Crypto,
+    CryptoSecrets,
+    Secrets,
+    Blocks,
+    Hashes,
+    Vulnerabilities,
+    AttackIds,
+    RegistryKeys,
+    Sql,
+    Regexes,
+    FilePaths,
+}
+
+impl Category {
+    pub fn all() -> Vec<Category> {
+        //! All the categories, in the order of the fields of `Artifacts`
+        vec![
+            Category::Urls,
+            Category::Domains,
+            Category::Emails,
+            Category::IpAddress,
+            Category::Endpoints,
+            Category::Crypto,
+            Category::CryptoSecrets,
+            Category::Secrets,
+            Category::Blocks,
+            Category::Hashes,
+            Category::Vulnerabilities,
+            Category::AttackIds,
+            Category::RegistryKeys,
+            Category::Sql,
+            Category::Regexes,
+            Category::FilePaths,
+        ]
+    }
+
+    pub fn name<'a>(&self) -> &'a str {
+        //! Name of the category; same as the field name in `Artifacts`
+        match *self {
+            Category::Urls => "urls",
+            Category::Domains => "domains",
+            Category::Emails => "emails",
+            Category::IpAddress => "ip_address",
+            Category::Endpoints => "endpoints",
+            Category::Crypto => "crypto",
+            Category::CryptoSecrets => "crypto_secrets",
+            Category::Secrets => "secrets",
+            Category::Blocks => "blocks",
+            Category::Hashes => "hashes",
+            Category::Vulnerabilities => "vulnerabilities",
+            Category::AttackIds => "attack_ids",
+            Category::RegistryKeys => "registry_keys",
+            Category::Sql => "sql",
+            Category::Regexes => "regexes",
+            Category::FilePaths => "file_paths",
+        }
+    }
+}
+
+/// IP Address version
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+/// Class of an IP Address; the special purpose block it is in (RFC 6890), or Public
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum IpClass {
+    Public,
+    /// RFC 1918 networks & IPv6 unique local addresses (fc00::/7)
+    Private,
+    Loopback,
+    LinkLocal,
+    /// Carrier-grade NAT shared address space (100.64.0.0/10)
+    Cgnat,
+    /// TEST-NET-1/2/3 & 2001:db8::/32
+    Documentation,
+    Multicast,
+    /// Reserved for future use, broadcast & anycast relay blocks
+    Reserved,
+    /// Never routed on the Internet: "this network", benchmarking, IETF protocol assignments
+    Bogon,
+}
+
+impl IpClass {
+    pub fn all() -> Vec<IpClass> {
+        //! All the classes
+        vec![
+            IpClass::Public,
+            IpClass::Private,
+            IpClass::Loopback,
+            IpClass::LinkLocal,
+            IpClass::Cgnat,
+            IpClass::Documentation,
+            IpClass::Multicast,
+            IpClass::Reserved,
+            IpClass::Bogon,
+        ]
+    }
+
+    pub fn name<'a>(&self) -> &'a str {
+        //! Name of the class, as serialized
+        match *self {
+            IpClass::Public => "public",
+            IpClass::Private => "private",
+            IpClass::Loopback => "loopback",
+            IpClass::LinkLocal => "link_local",
+            IpClass::Cgnat => "cgnat",
+            IpClass::Documentation => "documentation",
+            IpClass::Multicast => "multicast",
+            IpClass::Reserved => "reserved",
+            IpClass::Bogon => "bogon",
+        }
+    }
+}
+
+/// The details of an X.509 Certificate
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    /// The subject, eg: C=US, O=Example Corp, CN=www.example.com
+    pub subject: String,
+    pub issuer: String,
+    /// The serial number, in hex
+    pub serial: String,
+    /// SHA-1 fingerprint of the DER encoded certificate, in hex
+    pub sha1: String,
+    /// SHA-256 fingerprint of the DER encoded certificate, in hex
+    pub sha256: String,
+    /// The DNS names of the Subject Alternative Name extension
+    pub domains: Vec<String>,
+}
+
+/// A single extracted indicator with its structured fields
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Indicator {
+    Url {
+        value: String,
+    },
+    Domain {
+        value: String,
+    },
+    Email {
+        value: String,
+    },
+    Ip {
+        /// The IP Address, without the CIDR suffix
+        value: String,
+        version: IpVersion,
+        /// The CIDR prefix length, if given as a network (eg: 10.0.0.0/8)
+        cidr: Option<u8>,
+        /// Class of the address; of the network address for a CIDR
+        class: IpClass,
+    },
+    Endpoint {
+        /// The endpoint, eg: 10.0.0.1:8080 or [2001:db8::1]:443
+        value: String,
+        /// The IP Address (without brackets) or Domain Name
+        host: String,
+        port: u16,
+        /// Version of the IP Address; None if the host is a Domain Name
+        version: Option<IpVersion>,
+    },
+    Crypto {
+        value: String,
+        /// Name of the crypto currency, eg: Bitcoin
+        coin: String,
+    },
+    CryptoSecret {
+        value: String,
+        /// Kind of secret: Extended Public Key, Extended Private Key, WIF Private Key
+        /// or BIP-39 Mnemonic
+        kind: String,
+    },
+    Secret {
+        value: String,
+        /// Provider or kind of the secret, eg: AWS Access Key ID, JWT or Generic Secret
+        provider: String,
+        /// The decoded header of a JWT, eg: {"alg":"HS256","typ":"JWT"}
+        jwt_header: Option<String>,
+    },
+    Block {
+        value: String,
+        /// Kind of block, eg: Certificate, Public Key, PGP Public Key or SSH Public Key
+        kind: String,
+        /// Fingerprint of a Certificate, Public Key, SSH or PGP Public Key
+        fingerprint: Option<String>,
+        /// The details of a Certificate
+        certificate: Option<Certificate>,
+    },
+    Hash {
+        value: String,
+        /// Name of the hash algorithm, eg: SHA-256
+        algorithm: String,
+    },
+    Vulnerability {
+        value: String,
+        /// Kind of identifier: CVE, CWE, CAPEC or GHSA
+        kind: String,
+    },
+    AttackId {
+        value: String,
+        /// Kind of ATT&CK ID: Technique, Tactic, Group, Software or Mitigation
+        kind: String,
+    },
+    RegistryKey {
+        value: String,
+        /// The root key, eg: HKEY_LOCAL_MACHINE; None if the key is relative
+        hive: Option<String>,
+    },
+    Sql {
+        value: String,
+    },
+    Regex {
+        value: String,
+    },
+    FilePath {
+        value: String,
+    },
+}
+
+impl Indicator {
+    pub(crate) fn secret(value: &str, provider: &str) -> Indicator {
+        //! A Secret of the given provider; with the decoded header of a JWT
+        Indicator::Secret {
+            value: value.to_string(),
+            provider: provider.to_string(),
+            jwt_header: secret::jwt_header(value).filter(|_| provider == "JWT"),
+        }
+    }
+
+    pub(crate) fn block(value: &str, kind: &str) -> Indicator {
+        //! A Block of the given kind; with its fingerprint & the details of a Certificate
+        Indicator::Block {
+            value: value.to_string(),
+            kind: kind.to_string(),
+            fingerprint: block::fingerprint(value),
+            certificate: block::parse_certificate(value),
+        }
+    }
+
+    pub(crate) fn plain(category: Category, value: &str) -> Option<Indicator> {
+        //! Builds the typed Indicator of a value that is kept as is in its `Artifacts`
+        //! category, eg: an IP Address with its class; None for the categories that are
+        //! kept with their kind (`"value - kind"`), whose Indicators are built when found
+        let value = value.to_string();
+        let indicator = match category {
+            Category::Urls => Indicator::Url { value },
+            Category::Domains => Indicator::Domain { value },
+            Category::Emails => Indicator::Email { value },
+            Category::IpAddress => {
+                let (ip, cidr) = match value.split_once('/') {
+                    Some((ip, cidr)) => (ip.to_string(), Some(cidr.parse().ok()?)),
+                    None => (value, None),
+                };
+                let version = match network::which_ipv(&ip)? {
+                    "IPv4" => IpVersion::V4,
+                    _ => IpVersion::V6,
+                };
+                Indicator::Ip {
+                    class: network::which_ip_class(&ip)?,
+                    value: ip,
+                    version,
+                    cidr,
+                }
+            }
+            Category::Endpoints => {
+                let (host, port) = network::split_host_port(&value)?;
+                let version = match network::which_ipv(host) {
+                    Some("IPv4") => Some(IpVersion::V4),
+                    Some(_) => Some(IpVersion::V6),
+                    None => None,
+                };
+                Indicator::Endpoint {
+                    host: host.to_string(),
+                    port,
+                    version,
+                    value,
+                }
+            }
+            Category::Vulnerabilities => Indicator::Vulnerability {
+                kind: value.split('-').next()?.to_string(),
+                value,
+            },
+            Category::AttackIds => Indicator::AttackId {
+                kind: crate::validators::attack::which_attack_id(&value)?.to_string(),
+                value,
+            },
+            Category::RegistryKeys => Indicator::RegistryKey {
+                hive: hive(&value).map(|x| x.to_string()),
+                value,
+            },
+            Category::Sql => Indicator::Sql { value },
+            Category::Regexes => Indicator::Regex { value },
+            Category::FilePaths => Indicator::FilePath { value },
+            Category::Crypto
+            | Category::CryptoSecrets
+            | Category::Secrets
+            | Category::Blocks
+            | Category::Hashes => return None,
+        };
+        Some(indicator)
+    }
+
+    pub(crate) fn parse(category: Category, value: &str) -> Option<Indicator> {
+        //! Builds a typed Indicator back from a value as kept in the given `Artifacts`
+        //! category, eg: `"value - kind"`; only needed for `Artifacts` (eg: as read by the
+        //! CSV importer), as the extraction gives the typed Indicators of its matches
+        let indicator = match category {
+            Category::Crypto => {
+                let (value, coin) = value.rsplit_once(" - ")?;
+                Indicator::Crypto {
+                    value: value.to_string(),
+                    coin: coin.to_string(),
+                }
+            }
+            Category::CryptoSecrets => {
+                let (value, kind) = value.rsplit_once(" - ")?;
+                Indicator::CryptoSecret {
+                    value: value.to_string(),
+                    kind: kind.to_string(),
+                }
+            }
+            Category::Secrets => {
+                let (value, provider) = value.rsplit_once(" - ")?;
+                Indicator::secret(value, provider)
+            }
+            Category::Blocks => {
+                let (value, kind) = value.rsplit_once(" - ")?;
+                Indicator::block(value, kind)
+            }
+            Category::Hashes => {
+                let (value, algorithm) = value.rsplit_once(" - ")?;
+                Indicator::Hash {
+                    value: value.to_string(),
+                    algorithm: algorithm.to_string(),
+                }
+            }
+            _ => Indicator::plain(category, value)?,
+        };
+        Some(indicator)
+    }
+
+    pub fn category(&self) -> Category {
+        //! The `Artifacts` category this Indicator belongs to
+        match *self {
+            Indicator::Url { .. } => Category::Urls,
+            Indicator::Domain { .. } => Category::Domains,
+            Indicator::Email { .. } => Category::Emails,
+            Indicator::Ip { .. } => Category::IpAddress,
+            Indicator::Endpoint { .. } => Category::Endpoints,
+            Indicator::Crypto { .. } => Category::Crypto,
+            Indicator::CryptoSecret { .. } => Category::CryptoSecrets,
+            Indicator::Secret { .. } => Category::Secrets,
+            Indicator::Block { .. } => Category::Blocks,
+            Indicator::Hash { .. } => Category::Hashes,
+            Indicator::Vulnerability { .. } => Category::Vulnerabilities,
+            Indicator::AttackId { .. } => Category::AttackIds,
+            Indicator::RegistryKey { .. } => Category::RegistryKeys,
+            Indicator::Sql { .. } => Category::Sql,
+            Indicator::Regex { .. } => Category::Regexes,
+            Indicator::FilePath { .. } => Category::FilePaths,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        //! The indicator itself, eg: the URL, the crypto address or the hash
+        match self {
+            Indicator::Url { value }
+            | Indicator::Domain { value }
+            | Indicator::Email { value }
+            | Indicator::Ip { value, .. }
+            | Indicator::Endpoint { value, .. }
+            | Indicator::Crypto { value, .. }
+            | Indicator::CryptoSecret { value, .. }
+            | Indicator::Secret { value, .. }
+            | Indicator::Block { value, .. }
+            | Indicator::Hash { value, .. }
+            | Indicator::Vulnerability { value, .. }
+            | Indicator::AttackId { value, .. }
+            | Indicator::RegistryKey { value, .. }
+            | Indicator::Sql { value }
+            | Indicator::Regex { value }
+            | Indicator::FilePath { value } => value,
+        }
+    }
+
+    pub(crate) fn value_mut(&mut self) -> &mut String {
+        //! The indicator itself, to mask the secrets in it
+        match self {
+            Indicator::Url { value }
+            | Indicator::Domain { value }
+            | Indicator::Email { value }
+            | Indicator::Ip { value, .. }
+            | Indicator::Endpoint { value, .. }
+            | Indicator::Crypto { value, .. }
+            | Indicator::CryptoSecret { value, .. }
+            | Indicator::Secret { value, .. }
+            | Indicator::Block { value, .. }
+            | Indicator::Hash { value, .. }
+            | Indicator::Vulnerability { value, .. }
+            | Indicator::AttackId { value, .. }
+            | Indicator::RegistryKey { value, .. }
+            | Indicator::Sql { value }
+            | Indicator::Regex { value }
+            | Indicator::FilePath { value } => value,
+        }
+    }
+
+    pub(crate) fn to_artifact(&self) -> String {
+        //! The value as kept in its `Artifacts` category
+        match self {
+            Indicator::Ip {
+                value,
+                cidr: Some(cidr),
+                ..
+            } => format!("{}/{}", value, cidr),
+            Indicator::Crypto { value, coin } => format!("{} - {}", value, coin),
+            Indicator::CryptoSecret { value, kind } => format!("{} - {}", value, kind),
+            Indicator::Secret {
+                value, provider, ..
+            } => format!("{} - {}", value, provider),
+            Indicator::Block { value, kind, .. } => format!("{} - {}", value, kind),
+            Indicator::Hash { value, algorithm } => format!("{} - {}", value, algorithm),
+            _ => self.value().to_string(),
+        }
+    }
+}
+
+fn hive(key: &str) -> Option<&'static str> {
+    //! Full name of the root key of a given registry key
+    let root = key.trim_start_matches('\\').split('\\').next()?;
+    let hive = match root.to_uppercase().as_str() {
+        "HKLM" | "HKEY_LOCAL_MACHINE" => "HKEY_LOCAL_MACHINE",
+        "HKCU" | "HKEY_CURRENT_USER" => "HKEY_CURRENT_USER",
+        "HKCR" | "HKEY_CLASSES_ROOT" => "HKEY_CLASSES_ROOT",
+        "HKU" | "HKEY_USERS" => "HKEY_USERS",
+        "HKCC" | "HKEY_CURRENT_CONFIG" => "HKEY_CURRENT_CONFIG",
+        "HKPD" | "HKEY_PERFORMANCE_DATA" => "HKEY_PERFORMANCE_DATA",
+        _ => return None,
+    };
+    Some(hive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Indicator::parse(Category::IpAddress, "10.0.0.0/8"),
+            Some(Indicator::Ip {
+                value: "10.0.0.0".to_string(),
+                version: IpVersion::V4,
+                cidr: Some(8),
+                class: IpClass::Private
+            })
+        );
+        assert_eq!(
+            Indicator::parse(
+                Category::Crypto,
+                "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9 - Bitcoin"
+            ),
+            Some(Indicator::Crypto {
+                value: "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9".to_string(),
+                coin: "Bitcoin".to_string()
+            })
+        );
+        assert_eq!(
+            Indicator::parse(Category::RegistryKeys, "HKLM\\SOFTWARE\\Microsoft"),
+            Some(Indicator::RegistryKey {
+                value: "HKLM\\SOFTWARE\\Microsoft".to_string(),
+                hive: Some("HKEY_LOCAL_MACHINE".to_string())
+            })
+        );
+        assert_eq!(
+            Indicator::parse(Category::Endpoints, "[2001:db8::1]:443"),
+            Some(Indicator::Endpoint {
+                value: "[2001:db8::1]:443".to_string(),
+                host: "2001:db8::1".to_string(),
+                port: 443,
+                version: Some(IpVersion::V6)
+            })
+        );
+        assert_eq!(Indicator::parse(Category::Crypto, ""), None);
+        assert!(matches!(
+            Indicator::parse(Category::IpAddress, "1.1.1.1"),
+            Some(Indicator::Ip {
+                class: IpClass::Public,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_to_artifact() {
+        for (category, value) in [
+            (Category::IpAddress, "2001:0DB8:1234::/48"),
+            (Category::IpAddress, "::1"),
+            (Category::Hashes, "d41d8cd98f00b204e9800998ecf8427e - MD5"),
+            (Category::Urls, "https://example.com"),
+        ] {
+            let indicator = Indicator::parse(category, value).unwrap();
+            assert_eq!(indicator.category(), category);
+            assert_eq!(indicator.to_artifact(), value);
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let indicator = Indicator::Hash {
+            value: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+            algorithm: "MD5".to_string(),
+        };
+        let json = serde_json::to_string(&indicator).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"hash","value":"d41d8cd98f00b204e9800998ecf8427e","algorithm":"MD5"}"#
+        );
+        assert_eq!(serde_json::from_str::<Indicator>(&json).unwrap(), indicator);
+    }
+}
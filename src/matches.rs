@@ -0,0 +1,355 @@
+use crate::{
+    extractor::Extractor,
+    indicator::{Category, Indicator},
+    validators::secret,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A single indicator along with where it was found in the given string
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The indicator as extracted
+    pub value: String,
+    /// Byte offset where the indicator starts
+    pub start: usize,
+    /// Byte offset just past the end of the indicator
+    pub end: usize,
+    /// Line number (starting at 1) the indicator was found on
+    pub line: usize,
+    /// Column (in characters, starting at 1) the indicator starts at
+    pub column: usize,
+    /// Text surrounding the indicator, including the indicator itself
+    pub context: String,
+    /// Whether the indicator was written in a defanged form (eg: `hxxp://evil[.]com`)
+    /// & `value` holds the refanged indicator
+    #[serde(default)]
+    pub defanged: bool,
+    /// Whether the IP Address (or the IPv4 host of the URL) was written in an obfuscated
+    /// notation (eg: `0xC0A80101`) & `value` holds it in dotted-quad notation
+    #[serde(default)]
+    pub obfuscated: bool,
+    /// The typed Indicator, eg: with the coin of a Crypto Address or the header of a JWT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indicator: Option<Indicator>,
+}
+
+/// All different types of artifacts found in a given string, with their locations.
+///
+/// Unlike `Artifacts`, every occurrence is kept (no sorting or de-duplication),
+/// in the order it was found.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct Matches {
+    /// All found URLs in the given string
+    pub urls: Option<Vec<Match>>,
+    /// All found Domains in the given string
+    pub domains: Option<Vec<Match>>,
+    /// All found Email Addresses in the given string
+    pub emails: Option<Vec<Match>>,
+    /// All found IP Addresses in the given string
+    pub ip_address: Option<Vec<Match>>,
+    /// All found IP Addresses & Domains with a port (endpoints) in the given string
+    pub endpoints: Option<Vec<Match>>,
+    /// All found Crypto Addresses in the given string
+    pub crypto: Option<Vec<Match>>,
+    /// All found Crypto Wallet Secrets (extended keys, WIF private keys & BIP-39 mnemonics) in the given string
+    pub crypto_secrets: Option<Vec<Match>>,
+    /// All found Secrets (API keys, tokens, webhooks, JWTs & private keys) in the given string
+    pub secrets: Option<Vec<Match>>,
+    /// All found Certificates, Public Keys & PGP Blocks
+    pub blocks: Option<Vec<Match>>,
+    /// All found File Hashes in the given string
+    pub hashes: Option<Vec<Match>>,
+    /// All found Vulnerability Identifiers (CVE, CWE, CAPEC, GHSA) in the given string
+    pub vulnerabilities: Option<Vec<Match>>,
+    /// All found MITRE ATT&CK IDs (Techniques, Tactics, Groups, Software, Mitigations) in the given string
+    pub attack_ids: Option<Vec<Match>>,
+    /// All found Registry Keys in the given string
+    pub registry_keys: Option<Vec<Match>>,
+    /// All found SQL Statements in the given string
+    pub sql: Option<Vec<Match>>,
+    /// All found Regular Expressions in the given string
+    pub regexes: Option<Vec<Match>>,
+    /// All found File Paths in the given string
+    pub file_paths: Option<Vec<Match>>,
+}
+
+impl Matches {
+    pub fn from_str(s: &str, context: usize) -> Option<Self> {
+        //! Extracts Indicators from a given string, along with their
+        //! locations & up to `context` characters either side of them.
+        //!
+        //! ## Example Usage
+        //! ```rust
+        //! use ioc_extract::Matches;
+        //!
+        //! let x = "this is an IP address: 192.168.2.11";
+        //! let ips = Matches::from_str(x, 12).unwrap().ip_address.unwrap();
+        //! assert_eq!(ips[0].start, 23);
+        //! assert_eq!(ips[0].context, "IP address: 192.168.2.11");
+        //! ```
+        Extractor::new().context(context).matches(s)
+    }
+
+    pub fn get(&self, category: Category) -> Option<&Vec<Match>> {
+        //! All found matches of the given category
+        match category {
+            Category::Urls => self.urls.as_ref(),
+            Category::Domains => self.domains.as_ref(),
+            Category::Emails => self.emails.as_ref(),
+            Category::IpAddress => self.ip_address.as_ref(),
+            Category::Endpoints => self.endpoints.as_ref(),
+            Category::Crypto => self.crypto.as_ref(),
+            Category::CryptoSecrets => self.crypto_secrets.as_ref(),
+            Category::Secrets => self.secrets.as_ref(),
+            Category::Blocks => self.blocks.as_ref(),
+            Category::Hashes => self.hashes.as_ref(),
+            Category::Vulnerabilities => self.vulnerabilities.as_ref(),
+            Category::AttackIds => self.attack_ids.as_ref(),
+            Category::RegistryKeys => self.registry_keys.as_ref(),
+            Category::Sql => self.sql.as_ref(),
+            Category::Regexes => self.regexes.as_ref(),
+            Category::FilePaths => self.file_paths.as_ref(),
+        }
+    }
+
+    pub(crate) fn redact(&mut self) {
+        //! Masks the found Secrets & Crypto Wallet Secrets (but extended public keys) in
+        //! the values, typed Indicators & contexts of all the matches, eg: an API key in the
+        //! query of a URL or in the context of a nearby IP Address
+        let mut secrets: Vec<String> = [&self.secrets, &self.crypto_secrets]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|m| match &m.indicator {
+                Some(Indicator::Secret { value, .. }) => Some(value.clone()),
+                Some(Indicator::CryptoSecret { value, kind }) if kind != "Extended Public Key" => {
+                    Some(value.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        // the longest first, so a secret that contains another one is masked as a whole
+        secrets.sort_by_key(|x| std::cmp::Reverse(x.len()));
+        secrets.dedup();
+        let mask = |text: &mut String| {
+            for x in &secrets {
+                if text.contains(x.as_str()) {
+                    *text = text.replace(x.as_str(), &secret::redact(x));
+                }
+            }
+        };
+        for matches in [
+            &mut self.urls,
+            &mut self.domains,
+            &mut self.emails,
+            &mut self.ip_address,
+            &mut self.endpoints,
+            &mut self.crypto,
+            &mut self.crypto_secrets,
+            &mut self.secrets,
+            &mut self.blocks,
+            &mut self.hashes,
+            &mut self.vulnerabilities,
+            &mut self.attack_ids,
+            &mut self.registry_keys,
+            &mut self.sql,
+            &mut self.regexes,
+            &mut self.file_paths,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for m in matches.iter_mut() {
+                mask(&mut m.value);
+                mask(&mut m.context);
+                if let Some(indicator) = &mut m.indicator {
+                    mask(indicator.value_mut());
+                }
+            }
+        }
+    }
+
+    pub(crate) fn take(&mut self, category: Category) -> Option<Vec<Match>> {
+        //! Takes out all found matches of the given category
+        match category {
+            Category::Urls => self.urls.take(),
+            Category::Domains => self.domains.take(),
+            Category::Emails => self.emails.take(),
+            Category::IpAddress => self.ip_address.take(),
+            Category::Endpoints => self.endpoints.take(),
+            Category::Crypto => self.crypto.take(),
+            Category::CryptoSecrets => self.crypto_secrets.take(),
+            Category::Secrets => self.secrets.take(),
+            Category::Blocks => self.blocks.take(),
+            Category::Hashes => self.hashes.take(),
+            Category::Vulnerabilities => self.vulnerabilities.take(),
+            Category::AttackIds => self.attack_ids.take(),
+            Category::RegistryKeys => self.registry_keys.take(),
+            Category::Sql => self.sql.take(),
+            Category::Regexes => self.regexes.take(),
+            Category::FilePaths => self.file_paths.take(),
+        }
+    }
+
+    pub fn indicators(&self) -> impl Iterator<Item = (Indicator, &Match)> + '_ {
+        //! Iterates over all found matches along with their typed Indicators
+        Category::all().into_iter().flat_map(move |category| {
+            self.get(category)
+                .into_iter()
+                .flatten()
+                .filter_map(move |m| {
+                    let indicator = m.indicator.clone();
+                    Some((
+                        indicator.or_else(|| Indicator::plain(category, &m.value))?,
+                        m,
+                    ))
+                })
+        })
+    }
+
+    pub fn unique_indicators(&self) -> Vec<Indicator> {
+        //! The typed Indicators of all found matches, sorted & de-duplicated as the values
+        //! of `Artifacts` are; eg: to export the indicators of a report
+        let mut found: BTreeMap<(Category, &str), Indicator> = BTreeMap::new();
+        for (indicator, m) in self.indicators() {
+            found
+                .entry((indicator.category(), m.value.as_str()))
+                .or_insert(indicator);
+        }
+        found.into_values().collect()
+    }
+
+    /// Checks to see if nothing at all was found
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_none()
+            && self.domains.is_none()
+            && self.emails.is_none()
+            && self.ip_address.is_none()
+            && self.endpoints.is_none()
+            && self.crypto.is_none()
+            && self.crypto_secrets.is_none()
+            && self.secrets.is_none()
+            && self.blocks.is_none()
+            && self.hashes.is_none()
+            && self.vulnerabilities.is_none()
+            && self.attack_ids.is_none()
+            && self.registry_keys.is_none()
+            && self.sql.is_none()
+            && self.regexes.is_none()
+            && self.file_paths.is_none()
+    }
+}
+
+/// Locates sub-slices of a given string & builds a `Match` for them.
+pub(crate) struct Source<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+    context: usize,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str, context: usize) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        Source {
+            text,
+            line_starts,
+            context,
+        }
+    }
+
+    pub fn offset_of(&self, part: &str) -> usize {
+        //! Byte offset of a sub-slice of the source text
+        part.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+
+    pub fn locate(&self, value: String, start: usize, end: usize) -> Match {
+        //! Builds a `Match` for the given value found between the given byte offsets
+        let line = self.line_starts.partition_point(|&x| x <= start);
+        let line_start = self.line_starts[line - 1];
+        let column = self.text[line_start..start].chars().count() + 1;
+
+        let before = self.text[..start]
+            .char_indices()
+            .rev()
+            .take(self.context)
+            .last()
+            .map_or(start, |(i, _)| i);
+        let after = self.text[end..]
+            .char_indices()
+            .nth(self.context)
+            .map_or(self.text.len(), |(i, _)| end + i);
+
+        Match {
+            value,
+            start,
+            end,
+            line,
+            column,
+            context: self.text[before..after].to_string(),
+            defanged: false,
+            obfuscated: false,
+            indicator: None,
+        }
+    }
+
+    pub fn typed(&self, indicator: Indicator, start: usize, end: usize) -> Match {
+        //! Builds a `Match` for the given Indicator found between the given byte offsets;
+        //! its value is as kept in `Artifacts`, eg: `"value - kind"`
+        let mut m = self.locate(indicator.to_artifact(), start, end);
+        m.indicator = Some(indicator);
+        m
+    }
+}
+
+/// Sorts & de-duplicates the values of the found matches
+pub(crate) fn values(matches: Option<Vec<Match>>) -> Option<Vec<String>> {
+    let mut values: Vec<String> = matches?.into_iter().map(|m| m.value).collect();
+    values.sort();
+    values.dedup();
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let text = "first line\nthe ip 10.0.0.1 is here";
+        let src = Source::new(text, 4);
+        let start = text.find("10.0.0.1").unwrap();
+        let m = src.locate("10.0.0.1".to_string(), start, start + 8);
+        assert_eq!(m.start, 18);
+        assert_eq!(m.end, 26);
+        assert_eq!(m.line, 2);
+        assert_eq!(m.column, 8);
+        assert_eq!(m.context, " ip 10.0.0.1 is ");
+        assert_eq!(&text[m.start..m.end], m.value);
+    }
+
+    #[test]
+    fn test_locate_multibyte_context() {
+        let text = "清华大学 x.com 清华大学";
+        let src = Source::new(text, 2);
+        let start = text.find("x.com").unwrap();
+        let m = src.locate("x.com".to_string(), start, start + 5);
+        assert_eq!(m.line, 1);
+        assert_eq!(m.column, 6);
+        assert_eq!(m.context, "学 x.com 清");
+    }
+
+    #[test]
+    fn test_values() {
+        let m = |v: &str| Match {
+            value: v.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            values(Some(vec![m("b"), m("a"), m("b")])),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(values(None), None);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{extractor::Extractor, indicator::Category, matches::Match, Artifacts, Matches};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{BufRead, Error, ErrorKind, Result},
+    str::from_utf8,
+};
+
+/// Where a chunk starts in the whole input
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn advance(&mut self, text: &str) {
+        //! Moves past the given (processed) text
+        self.offset += text.len();
+        match text.rfind('\n') {
+            Some(i) => {
+                self.line += text.matches('\n').count();
+                self.column = text[i + 1..].chars().count();
+            }
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    fn relocate(&self, mut m: Match) -> Match {
+        //! Moves a Match found in a chunk to its place in the whole input
+        if m.line == 1 {
+            m.column += self.column;
+        }
+        m.line += self.line - 1;
+        m.start += self.offset;
+        m.end += self.offset;
+        m
+    }
+}
+
+/// Number of matches passed on so far per category, to apply the limit to the whole input
+#[derive(Default)]
+pub(crate) struct Counts(BTreeMap<Category, usize>);
+
+impl Counts {
+    pub(crate) fn emit<F>(
+        &mut self,
+        extractor: &Extractor,
+        mut matches: Matches,
+        relocate: impl Fn(Match) -> Match,
+        found: &mut F,
+    ) where
+        F: FnMut(Category, Match),
+    {
+        //! Passes on the matches found in a chunk, moved to their place in the whole input
+        for category in Category::all() {
+            for m in matches.take(category).into_iter().flatten() {
+                let count = self.0.entry(category).or_default();
+                if extractor.get_limit().is_some_and(|limit| *count >= limit) {
+                    break;
+                }
+                *count += 1;
+                found(category, relocate(m));
+            }
+        }
+    }
+}
+
+pub(crate) fn collect<F>(stream: F) -> Result<Option<Artifacts>>
+where
+    F: FnOnce(&mut dyn FnMut(Category, Match)) -> Result<()>,
+{
+    //! Collects the matches of a stream into Artifacts; only the (de-duplicated)
+    //! found indicators are kept in memory
+    let mut found: BTreeMap<Category, BTreeSet<String>> = BTreeMap::new();
+    stream(&mut |category, m| {
+        found.entry(category).or_default().insert(m.value);
+    })?;
+
+    if found.is_empty() {
+        return Ok(None);
+    }
+    let mut artifacts = Artifacts::default();
+    for (category, values) in found {
+        *artifacts.get_mut(category) = Some(values.into_iter().collect());
+    }
+    Ok(Some(artifacts))
+}
+
+/// How far a chunk may grow past the chunk size to keep a PEM block in one piece
+const MAX_BLOCK: usize = 1 << 16;
+
+fn open_block(chunk: &[u8]) -> Option<usize> {
+    //! Where the line of a PEM block that is not yet ended starts, if there is one;
+    //! blocks span several lines, so they are only found if not cut in two
+    let begin = chunk.windows(11).rposition(|x| x == b"-----BEGIN ")?;
+    if chunk[begin..].windows(9).any(|x| x == b"-----END ") {
+        return None;
+    }
+    Some(
+        chunk[..begin]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1),
+    )
+}
+
+fn cut(chunk: &[u8]) -> usize {
+    //! Where to cut a full chunk; after the last newline, else after the last
+    //! whitespace so that no word is split, else at the last character boundary
+    if let Some(i) = chunk.iter().rposition(|&b| b == b'\n') {
+        return i + 1;
+    }
+    if let Some(i) = chunk.iter().rposition(|b| b.is_ascii_whitespace()) {
+        return i + 1;
+    }
+    let mut i = chunk.len();
+    while i > 0 && (chunk[i - 1] & 0xC0) == 0x80 {
+        i -= 1;
+    }
+    // step back before the start byte of a (possibly) incomplete character
+    if i > 0 && chunk[i - 1] >= 0xC0 {
+        i -= 1;
+    }
+    if i == 0 {
+        chunk.len()
+    } else {
+        i
+    }
+}
+
+impl Extractor {
+    pub fn stream<R, F>(&self, mut reader: R, mut found: F) -> Result<()>
+    where
+        R: BufRead,
+        F: FnMut(Category, Match),
+    {
+        //! Extracts Indicators from a reader, in chunks of about `chunk_size` bytes,
+        //! calling `found` with every Match as soon as the chunk it is in is processed.
+        //! Chunks end at a newline (or whitespace for very long lines), & not inside a PEM
+        //! block, so indicators are never split across chunks; the offsets, lines & columns
+        //! are of the whole input.
+        //!
+        //! ## Example Usage
+        //! ```rust
+        //! use ioc_extract::Extractor;
+        //! use std::io::Cursor;
+        //!
+        //! let log = Cursor::new("GET / from 10.0.0.1\nGET /admin from 10.0.0.2\n");
+        //! Extractor::new()
+        //!     .stream(log, |category, m| println!("{:?} {} at line {}", category, m.value, m.line))
+        //!     .unwrap();
+        //! ```
+        let chunk_size = self.get_chunk_size();
+        let mut chunk: Vec<u8> = Vec::with_capacity(chunk_size);
+        let mut position = Position {
+            offset: 0,
+            line: 1,
+            column: 0,
+        };
+        let mut counts = Counts::default();
+
+        let mut process = |bytes: &[u8], position: &mut Position| -> Result<()> {
+            let text = from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            if let Some(matches) = self.matches(text) {
+                counts.emit(self, matches, |m| position.relocate(m), &mut found);
+            }
+            position.advance(text);
+            Ok(())
+        };
+
+        loop {
+            let buf = reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let take = buf
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(buf.len(), |i| i + 1);
+            chunk.extend_from_slice(&buf[..take]);
+            reader.consume(take);
+
+            if chunk.len() >= chunk_size {
+                // an open PEM block is processed with the rest of it, unless it is too long
+                let at = match open_block(&chunk) {
+                    Some(0) if chunk.len() < chunk_size + MAX_BLOCK => continue,
+                    Some(at) if at > 0 => at,
+                    _ => cut(&chunk),
+                };
+                process(&chunk[..at], &mut position)?;
+                chunk.drain(..at);
+            }
+        }
+        if !chunk.is_empty() {
+            process(&chunk, &mut position)?;
+        }
+        Ok(())
+    }
+
+    pub fn extract_reader<R: BufRead>(&self, reader: R) -> Result<Option<Artifacts>> {
+        //! Extracts Indicators from a reader, in chunks; only the (de-duplicated)
+        //! found indicators are kept in memory
+        collect(|found| self.stream(reader, found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const TEXT: &str = "first line has 10.0.0.1\nsecond: https://www.example.com and evil-domain.com\n\nthe end 10.0.0.2 清华大学 x 10.0.0.3";
+
+    fn collect(extractor: &Extractor, text: &str) -> Vec<(Category, Match)> {
+        let mut found = vec![];
+        extractor
+            .stream(Cursor::new(text), |category, m| found.push((category, m)))
+            .unwrap();
+        found.sort_by_key(|(_, m)| m.start);
+        found
+    }
+
+    #[test]
+    fn test_stream_same_as_whole() {
+        let whole = collect(&Extractor::new(), TEXT);
+        for chunk_size in [1, 5, 16, 64] {
+            let chunked = collect(&Extractor::new().chunk_size(chunk_size), TEXT);
+            assert_eq!(chunked, whole, "chunk size {}", chunk_size);
+        }
+        for (_, m) in whole {
+            assert_eq!(&TEXT[m.start..m.end], m.value);
+        }
+    }
+
+    #[test]
+    fn test_stream_pem_block() {
+        let text = "key of 10.0.0.1:\n-----BEGIN PUBLIC KEY-----\nMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEwzPbZUVjHbcJIfOnlQPZLLeB7f7r\nt6Kmj1u6eq9T/Be7XTNMzr0ou8rc+4/4ABhw+aPwN874g7oqZn09lGo0Eg==\n-----END PUBLIC KEY-----\nfrom 10.0.0.2";
+        let whole = collect(&Extractor::new(), text);
+        assert_eq!(whole.len(), 3);
+        assert_eq!(whole[1].0, Category::Blocks);
+        for chunk_size in [1, 16, 64] {
+            let chunked = collect(&Extractor::new().chunk_size(chunk_size), text);
+            assert_eq!(chunked, whole, "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_stream_long_line() {
+        let text = "a ".repeat(100) + "10.0.0.1 " + &"b ".repeat(100) + "10.0.0.2";
+        let found = collect(&Extractor::new().chunk_size(32), &text);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].1.column, 201);
+        assert_eq!(found[1].1.start, text.len() - 8);
+        assert_eq!(found[1].1.line, 1);
+    }
+
+    #[test]
+    fn test_stream_limit() {
+        let found = collect(&Extractor::new().chunk_size(1).limit(2), TEXT);
+        let values = |category| {
+            found
+                .iter()
+                .filter(|(x, _)| *x == category)
+                .map(|(_, m)| m.value.as_str())
+                .collect::<Vec<_>>()
+        };
+        // the limit is of the whole input; the 3rd IP Address is in a later chunk
+        assert_eq!(values(Category::IpAddress), vec!["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(values(Category::Urls), vec!["https://www.example.com"]);
+        assert_eq!(values(Category::Domains), vec!["evil-domain.com"]);
+        assert_eq!(found.len(), 4);
+    }
+
+    #[test]
+    fn test_extract_reader() {
+        let ioc = Extractor::new()
+            .chunk_size(8)
+            .extract_reader(Cursor::new(TEXT))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            ioc.ip_address.unwrap(),
+            vec!["10.0.0.1", "10.0.0.2", "10.0.0.3"]
+        );
+        assert_eq!(ioc.domains.unwrap(), vec!["evil-domain.com"]);
+    }
+
+    #[test]
+    fn test_cut() {
+        assert_eq!(cut(b"ab\ncd ef"), 3);
+        assert_eq!(cut(b"abcd ef"), 5);
+        assert_eq!(cut("ab清".as_bytes()), 2);
+        assert_eq!(cut(b"abcdef"), 6);
+    }
+
+    #[test]
+    fn test_open_block() {
+        assert_eq!(open_block(b"a\n-----BEGIN CERTIFICATE-----\nMIIB"), Some(2));
+        assert_eq!(
+            open_block(b"-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n"),
+            None
+        );
+        assert_eq!(open_block(b"no block\n"), None);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{
+    extractor::Extractor,
+    indicator::Category,
+    matches::Match,
+    stream::{collect, Counts},
+    Artifacts,
+};
+use std::{
+    fs::File,
+    io::{BufReader, ErrorKind, Read, Result},
+    path::Path,
+};
+
+/// A run of printable characters found in binary input
+#[derive(Debug, PartialEq)]
+struct Run {
+    /// Byte offset of the run in the chunk
+    offset: usize,
+    /// Bytes per character; 1 for ASCII, 2 for UTF-16LE
+    width: usize,
+    text: String,
+}
+
+fn is_printable(b: u8) -> bool {
+    b == b'\t' || (0x20..0x7f).contains(&b)
+}
+
+fn runs(bytes: &[u8], min_length: usize, last: bool) -> (Vec<Run>, usize) {
+    //! Finds the ASCII & UTF-16LE runs of at least `min_length` characters, like `strings`;
+    //! unless this is the `last` chunk, the runs that reach the end of the chunk may go on
+    //! in the next one, so they are left out & the offset where they start is returned
+    let mut found = vec![];
+    let mut pending = bytes.len();
+    let len = bytes.len();
+
+    let mut i = 0;
+    while i < len {
+        if !is_printable(bytes[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < len && is_printable(bytes[i]) {
+            i += 1;
+        }
+        if i == len && !last {
+            pending = pending.min(start);
+        } else if i - start >= min_length {
+            found.push(Run {
+                offset: start,
+                width: 1,
+                text: String::from_utf8_lossy(&bytes[start..i]).into_owned(),
+            });
+        }
+    }
+
+    let mut i = 0;
+    while i + 1 < len {
+        if !(is_printable(bytes[i]) && bytes[i + 1] == 0) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i + 1 < len && is_printable(bytes[i]) && bytes[i + 1] == 0 {
+            i += 2;
+        }
+        if i + 1 >= len && !last {
+            pending = pending.min(start);
+        } else if (i - start) / 2 >= min_length {
+            found.push(Run {
+                offset: start,
+                width: 2,
+                text: bytes[start..i]
+                    .iter()
+                    .step_by(2)
+                    .map(|&b| b as char)
+                    .collect(),
+            });
+        }
+    }
+
+    found.retain(|run| run.offset < pending);
+    found.sort_by_key(|run| run.offset);
+    (found, pending)
+}
+
+impl Extractor {
+    pub fn min_string_length(mut self, min_string_length: usize) -> Self {
+        //! Minimum number of characters of the strings taken from binary input; defaults to 4
+        self.min_string_length = min_string_length.max(1);
+        self
+    }
+
+    pub fn stream_bytes<R, F>(&self, mut reader: R, mut found: F) -> Result<()>
+    where
+        R: Read,
+        F: FnMut(Category, Match),
+    {
+        //! Extracts Indicators from binary input (eg: executables, memory dumps, pcaps),
+        //! calling `found` with every Match. Like `strings`, only the runs of printable ASCII
+        //! and UTF-16LE characters of at least `min_string_length` are looked at; each run
+        //! is a line of its own. The offsets are of the bytes in the whole input, the line is
+        //! the number of the string and the column is the character in that string.
+        //!
+        //! ## Example Usage
+        //! ```rust
+        //! use ioc_extract::Extractor;
+        //!
+        //! let mut binary = b"MZ\x90\x00\x03\x00http://evil-domain.com/a.exe\x00\xff".to_vec();
+        //! binary.extend("HKLM\\Software\\Run".encode_utf16().flat_map(|c| c.to_le_bytes()));
+        //! Extractor::new()
+        //!     .stream_bytes(&binary[..], |category, m| println!("{:?} {} at {}", category, m.value, m.start))
+        //!     .unwrap();
+        //! ```
+        let chunk_size = self.get_chunk_size();
+        let mut chunk: Vec<u8> = Vec::with_capacity(chunk_size);
+        let mut offset = 0;
+        let mut strings = 0;
+        let mut counts = Counts::default();
+
+        loop {
+            let wanted = chunk_size.saturating_sub(chunk.len()).max(1);
+            let read = reader
+                .by_ref()
+                .take(wanted as u64)
+                .read_to_end(&mut chunk)?;
+            let last = read < wanted;
+
+            let (found_runs, mut pending) = runs(&chunk, self.min_string_length, last);
+            let found_runs = if pending == 0 && !last {
+                // a single run fills the whole chunk; it has to be split
+                pending = chunk.len();
+                runs(&chunk, self.min_string_length, true).0
+            } else {
+                found_runs
+            };
+
+            let mut text = String::new();
+            let mut starts = vec![];
+            for run in &found_runs {
+                starts.push(text.len());
+                text.push_str(&run.text);
+                text.push('\n');
+            }
+            if let Some(matches) = self.matches(&text) {
+                let relocate = |mut m: Match| {
+                    let i = m.line - 1;
+                    let run = &found_runs[i];
+                    m.start = offset + run.offset + (m.start - starts[i]) * run.width;
+                    m.end = offset + run.offset + (m.end - starts[i]) * run.width;
+                    m.line += strings;
+                    m
+                };
+                counts.emit(self, matches, relocate, &mut found);
+            }
+            strings += found_runs.len();
+
+            chunk.drain(..pending);
+            offset += pending;
+            if last {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn extract_bytes(&self, bytes: &[u8]) -> Option<Artifacts> {
+        //! Extracts Indicators from the strings in binary input
+        collect(|found| self.stream_bytes(bytes, found))
+            .ok()
+            .flatten()
+    }
+
+    pub fn extract_binary_reader<R: Read>(&self, reader: R) -> Result<Option<Artifacts>> {
+        //! Extracts Indicators from the strings in binary input read from a reader, in chunks
+        collect(|found| self.stream_bytes(reader, found))
+    }
+
+    pub fn extract_file<P: AsRef<Path>>(&self, file: P) -> Result<Option<Artifacts>> {
+        //! Extracts Indicators from a given file, in chunks; files that are not
+        //! valid UTF-8 are read as binary input
+        let file = file.as_ref();
+        match self.extract_reader(BufReader::new(File::open(file)?)) {
+            Err(e) if e.kind() == ErrorKind::InvalidData => {
+                self.extract_binary_reader(BufReader::new(File::open(file)?))
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+    }
+
+    fn binary() -> Vec<u8> {
+        let mut bytes = b"MZ\x90\x00\x03\x00\x00\x00\x04\x00".to_vec();
+        bytes.extend(b"http://evil-domain.com/payload.exe\x00\xff\xfe\x01");
+        bytes.extend(utf16(
+            "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
+        ));
+        bytes.extend(b"\x00\x00\xe8\x12ab\x00cd\xff\x00\x00");
+        bytes.extend(utf16("10.0.0.1"));
+        bytes.extend(b"\x00\x00\xc3\x90 from 10.0.0.2\x90");
+        bytes
+    }
+
+    fn collect(extractor: &Extractor, bytes: &[u8]) -> Vec<(Category, Match)> {
+        let mut found = vec![];
+        extractor
+            .stream_bytes(bytes, |category, m| found.push((category, m)))
+            .unwrap();
+        found.sort_by_key(|(_, m)| m.start);
+        found
+    }
+
+    #[test]
+    fn test_runs() {
+        let bytes = binary();
+        let (found, pending) = runs(&bytes, 4, true);
+        let texts: Vec<&str> = found.iter().map(|x| x.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "http://evil-domain.com/payload.exe",
+                "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
+                "10.0.0.1",
+                " from 10.0.0.2"
+            ]
+        );
+        assert_eq!(pending, bytes.len());
+
+        let (found, pending) = runs(b"\x00abcdef\x00gh", 4, false);
+        assert_eq!(found.len(), 1);
+        assert_eq!(pending, 8);
+    }
+
+    #[test]
+    fn test_extract_bytes() {
+        let ioc = Extractor::new().extract_bytes(&binary()).unwrap();
+        assert_eq!(
+            ioc.urls.unwrap(),
+            vec!["http://evil-domain.com/payload.exe"]
+        );
+        assert_eq!(
+            ioc.registry_keys.unwrap(),
+            vec!["HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows\\CurrentVersion\\Run"]
+        );
+        assert_eq!(ioc.ip_address.unwrap(), vec!["10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    fn test_stream_bytes_offsets() {
+        let bytes = binary();
+        let whole = collect(&Extractor::new(), &bytes);
+        for (_, m) in &whole {
+            let raw = &bytes[m.start..m.end];
+            if raw.contains(&0) {
+                assert_eq!(utf16(&m.value), raw);
+            } else {
+                assert_eq!(raw, m.value.as_bytes());
+            }
+        }
+        for chunk_size in [130, 200, 1024] {
+            let chunked = collect(&Extractor::new().chunk_size(chunk_size), &bytes);
+            assert_eq!(chunked, whole, "chunk size {}", chunk_size);
+        }
+        // strings longer than a chunk are split
+        let chunked = collect(&Extractor::new().chunk_size(16), &bytes);
+        assert!(chunked.iter().all(|(c, _)| *c != Category::RegistryKeys));
+    }
+
+    #[test]
+    fn test_min_string_length() {
+        let bytes = b"\x00\x00www.evil-domain.com\x00\x00";
+        assert!(Extractor::new().extract_bytes(bytes).is_some());
+        assert!(Extractor::new()
+            .min_string_length(20)
+            .extract_bytes(bytes)
+            .is_none());
+    }
+
+    #[test]
+    fn test_from_file_binary() {
+        // unique to the test process, as test runs may be in parallel
+        let path = std::env::temp_dir().join(format!(
+            "ioc_extract_test_binary_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, binary()).unwrap();
+        let ioc = Artifacts::from_file(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(ioc.ip_address.unwrap(), vec!["10.0.0.1", "10.0.0.2"]);
+    }
+}
@@ -0,0 +1,218 @@
+use fancy_regex::Regex;
+use std::{
+    collections::HashSet,
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+    path::Path,
+    sync::RwLock,
+};
+
+lazy_static! {
+    /// Technique & Sub-Technique Regex Pattern (T1059, T1059.001)
+    static ref TECHNIQUE: Regex = Regex::new(r"^T\d{4}(?:\.\d{3})?$").unwrap();
+    /// Tactic Regex Pattern
+    static ref TACTIC: Regex = Regex::new(r"^TA\d{4}$").unwrap();
+    /// Group Regex Pattern
+    static ref GROUP: Regex = Regex::new(r"^G\d{4}$").unwrap();
+    /// Software Regex Pattern
+    static ref SOFTWARE: Regex = Regex::new(r"^S\d{4}$").unwrap();
+    /// Mitigation Regex Pattern
+    static ref MITIGATION: Regex = Regex::new(r"^M\d{4}$").unwrap();
+
+    /// ATT&CK IDs known to exist; the bundled list unless replaced with `load_attack_ids`
+    static ref KNOWN_IDS: RwLock<KnownIds> = RwLock::new(KnownIds::new(
+        parse_attack_ids(include_str!("attack_ids.txt")).unwrap()
+    ));
+}
+
+/// The known ATT&CK IDs
+struct KnownIds {
+    ids: HashSet<String>,
+    /// Techniques whose sub-techniques are listed, eg: T1059 if T1059.001 is listed
+    parents: HashSet<String>,
+}
+
+impl KnownIds {
+    fn new(ids: HashSet<String>) -> Self {
+        let parents = ids
+            .iter()
+            .filter_map(|id| id.split_once('.').map(|(parent, _)| parent.to_string()))
+            .collect();
+        KnownIds { ids, parents }
+    }
+}
+
+enum Type {
+    Technique,
+    Tactic,
+    Group,
+    Software,
+    Mitigation,
+}
+
+impl Type {
+    fn name<'a>(&self) -> &'a str {
+        match *self {
+            Type::Technique => "Technique",
+            Type::Tactic => "Tactic",
+            Type::Group => "Group",
+            Type::Software => "Software",
+            Type::Mitigation => "Mitigation",
+        }
+    }
+
+    fn pattern<'a>(&self) -> &'a Regex {
+        match *self {
+            Type::Technique => &TECHNIQUE,
+            Type::Tactic => &TACTIC,
+            Type::Group => &GROUP,
+            Type::Software => &SOFTWARE,
+            Type::Mitigation => &MITIGATION,
+        }
+    }
+
+    fn all() -> Vec<Type> {
+        vec![
+            Type::Technique,
+            Type::Tactic,
+            Type::Group,
+            Type::Software,
+            Type::Mitigation,
+        ]
+    }
+}
+
+fn parse_attack_ids(list: &str) -> Result<HashSet<String>> {
+    //! Parses a list of ATT&CK IDs; one ID or `FIRST..LAST` range per line
+    let mut ids = HashSet::new();
+    for line in list.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (first, last) = line.split_once("..").unwrap_or((line, line));
+        let invalid = || {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid ATT&CK ID: {}", line),
+            )
+        };
+        if !is_attack_id(first) || !is_attack_id(last) {
+            return Err(invalid());
+        }
+        let digits = first
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (prefix, lo) = first.split_at(digits);
+        let hi = last.strip_prefix(prefix).ok_or_else(invalid)?;
+        if lo.contains('.') || hi.contains('.') || lo.len() != hi.len() {
+            if first != last {
+                return Err(invalid());
+            }
+            ids.insert(first.to_string());
+            continue;
+        }
+        let (lo, hi): (u32, u32) = (
+            lo.parse().map_err(|_| invalid())?,
+            hi.parse().map_err(|_| invalid())?,
+        );
+        for n in lo..=hi {
+            ids.insert(format!(
+                "{}{:0width$}",
+                prefix,
+                n,
+                width = last.len() - prefix.len()
+            ));
+        }
+    }
+    Ok(ids)
+}
+
+pub fn load_attack_ids<P: AsRef<Path>>(file: P) -> Result<()> {
+    //! Replaces the bundled list of known ATT&CK IDs with the list in the given file;
+    //! one ID or `FIRST..LAST` range per line, `#` for comments.
+    let ids = parse_attack_ids(&read_to_string(file)?)?;
+    *KNOWN_IDS.write().unwrap() = KnownIds::new(ids);
+    Ok(())
+}
+
+pub fn is_attack_id(value: &str) -> bool {
+    //! Check if the given value looks like any ATT&CK ID.
+    which_attack_id(value).is_some()
+}
+
+pub fn is_known_attack_id(value: &str) -> bool {
+    //! Check if the given value is an ATT&CK ID that is in the list of known IDs.
+    if !is_attack_id(value) {
+        return false;
+    }
+    let known = KNOWN_IDS.read().unwrap();
+    if known.ids.contains(value) {
+        return true;
+    }
+    // a sub-technique is known by its parent, unless
+    // the sub-techniques of the parent are listed
+    match value.split_once('.') {
+        Some((parent, _)) => known.ids.contains(parent) && !known.parents.contains(parent),
+        None => false,
+    }
+}
+
+pub fn which_attack_id(value: &str) -> Option<&str> {
+    //! Output the kind of ATT&CK ID (Technique, Tactic, Group, Software, Mitigation).
+    for id in Type::all() {
+        if id.pattern().is_match(value).unwrap_or_default() {
+            return Some(id.name());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_which_attack_id() {
+        assert_eq!(which_attack_id("T1059"), Some("Technique"));
+        assert_eq!(which_attack_id("T1059.001"), Some("Technique"));
+        assert_eq!(which_attack_id("TA0002"), Some("Tactic"));
+        assert_eq!(which_attack_id("G0016"), Some("Group"));
+        assert_eq!(which_attack_id("S0154"), Some("Software"));
+        assert_eq!(which_attack_id("M1036"), Some("Mitigation"));
+        assert_eq!(which_attack_id("T99999"), None);
+        assert_eq!(which_attack_id("T1059.1"), None);
+        assert_eq!(which_attack_id("t1059"), None);
+    }
+
+    #[test]
+    fn test_is_known_attack_id() {
+        assert!(is_known_attack_id("T1059"));
+        assert!(is_known_attack_id("T1059.001"));
+        assert!(is_known_attack_id("TA0043"));
+        assert!(is_known_attack_id("T0855"));
+        assert!(!is_known_attack_id("T9999"));
+        assert!(!is_known_attack_id("TA0099"));
+        assert!(!is_known_attack_id("T99999"));
+        // revoked technique & made-up sub-technique of a listed parent
+        assert!(!is_known_attack_id("T1013"));
+        assert!(!is_known_attack_id("T1059.999"));
+    }
+
+    #[test]
+    fn test_parse_attack_ids() {
+        let ids = parse_attack_ids("# comment\nT1001..T1003\n\nT1059.001\nG0016").unwrap();
+        assert_eq!(ids.len(), 5);
+        assert!(ids.contains("T1002"));
+        assert!(ids.contains("T1059.001"));
+        assert!(parse_attack_ids("T1001..G0003").is_err());
+        assert!(parse_attack_ids("X1234").is_err());
+    }
+
+    #[test]
+    fn test_known_ids() {
+        let known = KnownIds::new(parse_attack_ids("T1059\nT1059.001\nT1003").unwrap());
+        assert!(known.parents.contains("T1059"));
+        assert!(!known.parents.contains("T1003"));
+    }
+}
@@ -1,33 +1,49 @@
+use super::encoding::{
+    base32_decode, base58_decode, base58check_decode, base64_decode, bech32_decode,
+    cashaddr_verify, convert_bits, crc16_xmodem, monero_base58_decode, segwit_decode, Bech32,
+    BITCOIN_ALPHABET, RIPPLE_ALPHABET,
+};
+use blake2::Blake2b512;
 use fancy_regex::Regex;
+use sha3::{Digest, Keccak256};
 
 lazy_static! {
     /// Bitcoin Regex Pattern
-    static ref BTC: Regex = Regex::new(r"(?i)^[13][a-km-zA-HJ-NP-Z1-9]{26,33}|bc1[a-z0-9]{39,59}\b").unwrap();
+    static ref BTC: Regex = Regex::new(r"^(?:[13][1-9A-HJ-NP-Za-km-z]{25,34}|(?i:bc1[02-9ac-hj-np-z]{11,71}))$").unwrap();
     /// Bitcoin Cash Regex Pattern
-    static ref BCH: Regex = Regex::new(r"(?i)^(((bitcoincash|bchreg|bchtest):)?(?:q|p)[a-z0-9]{41}|[13][a-km-zA-HJ-NP-Z1-9]{33})\b").unwrap();
+    static ref BCH: Regex = Regex::new(r"^(?i:(?:bitcoincash:)?[qp][02-9ac-hj-np-z]{41})$").unwrap();
     /// Ethereum Regex Pattern
-    static ref ETH: Regex = Regex::new(r"(?i)^0x[a-fA-F0-9]{40}\b").unwrap();
+    static ref ETH: Regex = Regex::new(r"^0x[0-9a-fA-F]{40}$").unwrap();
     /// Litecoin Regex Pattern
-    static ref LTC: Regex = Regex::new(r"(?i)^(?:ltc1|[LM])(?=\S*?\d\S*?\b)[a-km-zA-HJ-NP-Z1-9]{26,33}\b").unwrap();
+    static ref LTC: Regex = Regex::new(r"^(?:[LM][1-9A-HJ-NP-Za-km-z]{26,33}|(?i:ltc1[02-9ac-hj-np-z]{11,71}))$").unwrap();
     /// Dodge Coin Regex Pattern
-    static ref DODGE: Regex = Regex::new(r"(?i)^D[5-9A-HJ-NP-U][1-9a-km-zA-HJ-NP-Z]{32}\b").unwrap();
+    static ref DODGE: Regex = Regex::new(r"^[D9A][1-9A-HJ-NP-Za-km-z]{25,34}$").unwrap();
     /// Dash Regex Pattern
-    static ref DASH: Regex = Regex::new(r"(?i)^X[1-9A-HJ-NP-Za-km-z]{33}\b").unwrap();
+    static ref DASH: Regex = Regex::new(r"^[X7][1-9A-HJ-NP-Za-km-z]{33}$").unwrap();
     /// Monero Regex Pattern
-    static ref XMR: Regex = Regex::new(r"(?i)^[48][0-9AB][1-9A-HJ-NP-Za-km-z]{93}\b").unwrap();
+    static ref XMR: Regex = Regex::new(r"^[48][1-9A-HJ-NP-Za-km-z]{94}(?:[1-9A-HJ-NP-Za-km-z]{11})?$").unwrap();
     /// Neo Regex Pattern
-    static ref NEO: Regex = Regex::new(r"(?i)^A[0-9a-zA-Z]{33}\b").unwrap();
+    static ref NEO: Regex = Regex::new(r"^A[1-9A-HJ-NP-Za-km-z]{33}$").unwrap();
     /// Ripple Regex Pattern
-    static ref XRP: Regex = Regex::new(r"(?i)^[rx](?=\S*?\d\S*?\b)[0-9a-zA-Z]{33,47}\b").unwrap();
-
-    /// Incase of Illegal chars for a Crypto Wallet
-    static ref ILLEGAL_CHARS: Vec<String> = [
-        '.', '!', '%', '*', '$', '#', '@', ')', '(', '^', '`', '~', '|',
-        '>', '<', '-', '_', '"', '\\', '}', '{', ':', ';', ',', '/', '?',
-    ]
-        .iter()
-        .map(|c|c.to_string())
-        .collect();
+    static ref XRP: Regex = Regex::new(r"^r[1-9A-HJ-NP-Za-km-z]{24,34}$").unwrap();
+    /// Tron Regex Pattern
+    static ref TRX: Regex = Regex::new(r"^T[1-9A-HJ-NP-Za-km-z]{33}$").unwrap();
+    /// Cardano (Shelley) Regex Pattern
+    static ref ADA: Regex = Regex::new(r"^(?i:addr1[02-9ac-hj-np-z]{53,120})$").unwrap();
+    /// Zcash Regex Pattern (transparent, Sapling & unified addresses)
+    static ref ZEC: Regex = Regex::new(r"^(?:t[13][1-9A-HJ-NP-Za-km-z]{33}|(?i:zs1[02-9ac-hj-np-z]{75})|(?i:u1[02-9ac-hj-np-z]{100,}))$").unwrap();
+    /// Polkadot (SS58) Regex Pattern
+    static ref DOT: Regex = Regex::new(r"^1[1-9A-HJ-NP-Za-km-z]{46,47}$").unwrap();
+    /// Cosmos Regex Pattern
+    static ref ATOM: Regex = Regex::new(r"^(?i:cosmos1[02-9ac-hj-np-z]{38,58})$").unwrap();
+    /// Tezos Regex Pattern
+    static ref XTZ: Regex = Regex::new(r"^(?:tz[1-4]|KT1)[1-9A-HJ-NP-Za-km-z]{33}$").unwrap();
+    /// Stellar Regex Pattern
+    static ref XLM: Regex = Regex::new(r"^G[A-Z2-7]{55}$").unwrap();
+    /// TON Regex Pattern (user-friendly & raw addresses)
+    static ref TON: Regex = Regex::new(r"^(?:[EU][0-9A-Za-z_-]{47}|[EU][0-9A-Za-z+/]{47}|(?:0|-1):[0-9a-fA-F]{64})$").unwrap();
+    /// Solana Regex Pattern
+    static ref SOL: Regex = Regex::new(r"^[1-9A-HJ-NP-Za-km-z]{32,44}$").unwrap();
 }
 
 enum Type {
@@ -40,6 +56,15 @@ enum Type {
     Monero,
     Neo,
     Ripple,
+    Tron,
+    Cardano,
+    Zcash,
+    Polkadot,
+    Cosmos,
+    Tezos,
+    Stellar,
+    Ton,
+    Solana,
 }
 
 impl Type {
@@ -54,6 +79,15 @@ impl Type {
             Type::Monero => "Monero",
             Type::Neo => "Neo",
             Type::Ripple => "Ripple",
+            Type::Tron => "Tron",
+            Type::Cardano => "Cardano",
+            Type::Zcash => "Zcash",
+            Type::Polkadot => "Polkadot",
+            Type::Cosmos => "Cosmos",
+            Type::Tezos => "Tezos",
+            Type::Stellar => "Stellar",
+            Type::Ton => "TON",
+            Type::Solana => "Solana",
         }
     }
 
@@ -68,6 +102,61 @@ impl Type {
             Type::Monero => &XMR,
             Type::Neo => &NEO,
             Type::Ripple => &XRP,
+            Type::Tron => &TRX,
+            Type::Cardano => &ADA,
+            Type::Zcash => &ZEC,
+            Type::Polkadot => &DOT,
+            Type::Cosmos => &ATOM,
+            Type::Tezos => &XTZ,
+            Type::Stellar => &XLM,
+            Type::Ton => &TON,
+            Type::Solana => &SOL,
+        }
+    }
+
+    fn verify(&self, value: &str) -> bool {
+        //! Decodes the address & verifies its checksum & version
+        match *self {
+            Type::Bitcoin => {
+                matches!(version(value, BITCOIN_ALPHABET), Some(0x00 | 0x05))
+                    || segwit_decode(value, "bc").is_some()
+            }
+            Type::BitcoinCash => cashaddr_verify(value, "bitcoincash"),
+            Type::Ethereum => is_eip55(value),
+            Type::Litecoin => {
+                matches!(version(value, BITCOIN_ALPHABET), Some(0x30 | 0x32))
+                    || segwit_decode(value, "ltc").is_some()
+            }
+            Type::Dodge => matches!(version(value, BITCOIN_ALPHABET), Some(0x1e | 0x16)),
+            Type::Dash => matches!(version(value, BITCOIN_ALPHABET), Some(0x4c | 0x10)),
+            Type::Monero => is_monero_checksum(value),
+            Type::Neo => version(value, BITCOIN_ALPHABET) == Some(0x17),
+            Type::Ripple => version(value, RIPPLE_ALPHABET) == Some(0x00),
+            Type::Tron => version(value, BITCOIN_ALPHABET) == Some(0x41),
+            Type::Cardano => verify_cardano(value),
+            Type::Zcash => verify_zcash(value),
+            Type::Polkadot => is_ss58(value, 0),
+            Type::Cosmos => bech32_bytes(value, "cosmos", Bech32::Bech32)
+                .is_some_and(|data| matches!(data.len(), 20 | 32)),
+            Type::Tezos => base58check_decode(value, BITCOIN_ALPHABET).is_some_and(|payload| {
+                payload.len() == 23
+                    && [
+                        [0x06, 0xa1, 0x9f],
+                        [0x06, 0xa1, 0xa1],
+                        [0x06, 0xa1, 0xa4],
+                        [0x06, 0xa1, 0xa6],
+                        [0x02, 0x5a, 0x79],
+                    ]
+                    .contains(&[payload[0], payload[1], payload[2]])
+            }),
+            Type::Stellar => base32_decode(value).is_some_and(|data| {
+                // version byte of an account ID (`G`), ed25519 key & CRC-16 (little endian)
+                data.len() == 35
+                    && data[0] == 6 << 3
+                    && crc16_xmodem(&data[..33]).to_le_bytes() == data[33..]
+            }),
+            Type::Ton => verify_ton(value),
+            Type::Solana => verify_solana(value),
         }
     }
 
@@ -82,87 +171,156 @@ impl Type {
             Type::Monero,
             Type::Neo,
             Type::Ripple,
+            Type::Tron,
+            Type::Cardano,
+            Type::Zcash,
+            Type::Polkadot,
+            Type::Cosmos,
+            Type::Tezos,
+            Type::Stellar,
+            Type::Ton,
+            // Solana addresses have no checksum & share the Base58 lengths of other coins,
+            // so they are only tried once no other coin has matched
+            Type::Solana,
         ]
     }
 }
 
-/// Evaluate CryptoCurrency & Validate
-fn validate(value: &str) -> bool {
-    // have a min of 15 chars at-least before evaluating
-    if value.chars().count() > 15
-        && value.chars().any(|c| c.is_ascii_digit())
-        && !value.is_empty()
-        && !ILLEGAL_CHARS.iter().any(|c| value.contains(c))
+fn version(value: &str, alphabet: &[u8; 58]) -> Option<u8> {
+    //! Version byte of a Base58Check address of a 160-bit hash (eg: 0x00 for Bitcoin P2PKH)
+    let payload = base58check_decode(value, alphabet)?;
+    (payload.len() == 21).then_some(payload[0])
+}
+
+fn is_eip55(value: &str) -> bool {
+    //! Verifies the EIP-55 mixed-case checksum of an Ethereum address;
+    //! all lowercase & all uppercase addresses have no checksum
+    let hex = &value[2..];
+    if !hex.bytes().any(|c| c.is_ascii_lowercase()) || !hex.bytes().any(|c| c.is_ascii_uppercase())
     {
-        for cryptocurrency in Type::all() {
-            if let Ok(_) = cryptocurrency.pattern().is_match(value) {
-                return true;
-            } else {
-                return false;
-            }
-        }
+        return true;
     }
-    false
+    let hash = Keccak256::digest(hex.to_ascii_lowercase().as_bytes());
+    hex.bytes().enumerate().all(|(i, c)| {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
 }
 
-pub fn is_bitcoin(value: &str) -> bool {
-    //! Check if the given crypto address is Bitcoin.
-    validate(value)
+fn is_monero_checksum(value: &str) -> bool {
+    //! Verifies the Keccak-256 checksum & network byte of a Monero address
+    //! (standard, sub-address or integrated)
+    let decoded = match monero_base58_decode(value) {
+        Some(decoded) => decoded,
+        None => return false,
+    };
+    let network = match decoded.len() {
+        69 => [18, 42].contains(&decoded[0]),
+        77 => decoded[0] == 19,
+        _ => false,
+    };
+    let (data, checksum) = decoded.split_at(decoded.len() - 4);
+    network && Keccak256::digest(data)[..4] == *checksum
 }
 
-pub fn is_bitcoin_cash(value: &str) -> bool {
-    //! Check if the given crypto address is Bitcoin Cash.
-    validate(value)
+fn bech32_bytes(value: &str, hrp: &str, variant: Bech32) -> Option<Vec<u8>> {
+    //! Bytes of a Bech32 string of the given human readable part & checksum variant
+    let (found, checksum, data) = bech32_decode(value)?;
+    if found != hrp || checksum != variant {
+        return None;
+    }
+    convert_bits(&data, 5, 8, false)
 }
 
-pub fn is_ethereum(value: &str) -> bool {
-    //! Check if the given crypto address is Ethereum.
-    validate(value)
+fn verify_cardano(value: &str) -> bool {
+    //! Verifies a mainnet Shelley address; the header byte holds the address type
+    //! (base, pointer or enterprise) & the network, followed by 28-byte key hashes
+    let data = match bech32_bytes(value, "addr", Bech32::Bech32) {
+        Some(data) if !data.is_empty() => data,
+        _ => return false,
+    };
+    data[0] & 0x0f == 1
+        && match data[0] >> 4 {
+            0..=3 => data.len() == 57,
+            4 | 5 => data.len() > 29,
+            6 | 7 => data.len() == 29,
+            _ => false,
+        }
 }
 
-pub fn is_litecoin(value: &str) -> bool {
-    //! Check if the given crypto address is Litecoin.
-    validate(value)
+fn verify_zcash(value: &str) -> bool {
+    //! Verifies a transparent (`t1`, `t3`), Sapling (`zs1`) or unified (`u1`) Zcash address
+    if value.starts_with('t') {
+        return base58check_decode(value, BITCOIN_ALPHABET).is_some_and(|payload| {
+            payload.len() == 22 && [[0x1c, 0xb8], [0x1c, 0xbd]].contains(&[payload[0], payload[1]])
+        });
+    }
+    bech32_bytes(value, "zs", Bech32::Bech32).is_some_and(|data| data.len() == 43)
+        || bech32_bytes(value, "u", Bech32::Bech32m).is_some()
 }
 
-pub fn is_dogecoin(value: &str) -> bool {
-    //! Check if the given crypto address is Dodgecoin.
-    validate(value)
+fn is_ss58(value: &str, prefix: u8) -> bool {
+    //! Verifies an SS58 address of the given network prefix (eg: 0 for Polkadot); the
+    //! checksum is the first 2 bytes of the Blake2b-512 of `SS58PRE`, the prefix & the key
+    let data = match base58_decode(value, BITCOIN_ALPHABET) {
+        Some(data) if data.len() == 35 && data[0] == prefix => data,
+        _ => return false,
+    };
+    let hash = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(&data[..33])
+        .finalize();
+    hash[..2] == data[33..]
 }
 
-pub fn is_dash(value: &str) -> bool {
-    //! Check if the given crypto address is Dash.
-    validate(value)
+fn verify_ton(value: &str) -> bool {
+    //! Verifies a TON address; either raw (`workchain:hex`), or user-friendly with
+    //! the bounceable flag, the workchain (0 or -1), the account ID & a CRC-16
+    if value.contains(':') {
+        return true;
+    }
+    let data = match base64_decode(value) {
+        Some(data) if data.len() == 36 => data,
+        _ => return false,
+    };
+    matches!(data[0], 0x11 | 0x51)
+        && matches!(data[1], 0x00 | 0xff)
+        && crc16_xmodem(&data[..34]).to_be_bytes() == data[34..]
 }
 
-pub fn is_monero(value: &str) -> bool {
-    //! Check if the given crypto address is Monero.
-    validate(value)
+fn verify_solana(value: &str) -> bool {
+    //! Checks that the address decodes to a 32-byte ed25519 key; as there is no checksum,
+    //! it must also mix digits, lower & upper case to rule out ordinary words & identifiers
+    value.bytes().any(|c| c.is_ascii_digit())
+        && value.bytes().any(|c| c.is_ascii_lowercase())
+        && value.bytes().any(|c| c.is_ascii_uppercase())
+        && base58_decode(value, BITCOIN_ALPHABET).is_some_and(|data| data.len() == 32)
 }
 
-pub fn is_neo(value: &str) -> bool {
-    //! Check if the given crypto address is Neo.
-    validate(value)
+/// Evaluate CryptoCurrency & Validate
+fn validate(value: &str, cryptocurrency: &Type) -> bool {
+    cryptocurrency.pattern().is_match(value).unwrap_or_default() && cryptocurrency.verify(value)
 }
 
-pub fn is_ripple(value: &str) -> bool {
-    //! Check if the given crypto address is Ripple.
-    validate(value)
+pub fn is_solana(value: &str) -> bool {
+    //! Check if the given crypto address is Solana.
+    validate(value, &Type::Solana)
 }
 
-pub fn is_cryptocurrency_any(value: &str) -> bool {
-    //! Check if the given string is a Crypto Currency.
-    validate(value)
+pub fn is_labelled_solana(value: &str, label: &str) -> bool {
+    //! Check if the given value is a Solana address labelled as one (eg: `SOL:`); any
+    //! random Base58 ID of 32 bytes looks like a Solana address, so in text it can only be
+    //! told apart by its label.
+    let label = label.trim_end_matches([':', '=']);
+    is_solana(value) && (label.eq_ignore_ascii_case("sol") || label.eq_ignore_ascii_case("solana"))
 }
 
 pub fn which_cryptocurrency(value: &str) -> Option<&str> {
     //! Output the Crypto Currency Name given the cryptocurrencty address.
-    for cryptocurrency in Type::all() {
-        if cryptocurrency.pattern().is_match(value).unwrap_or_default() {
-            return Some(cryptocurrency.name());
-        }
-    }
-    None
+    Type::all()
+        .into_iter()
+        .find(|cryptocurrency| validate(value, cryptocurrency))
+        .map(|cryptocurrency| cryptocurrency.name())
 }
 
 #[cfg(test)]
@@ -213,61 +371,336 @@ mod tests {
             which_cryptocurrency("rUocf1ixKzTuEe34kmVhRvGqNCofY1NJzV"),
             Some("Ripple")
         );
+        // Tron
+        assert_eq!(
+            which_cryptocurrency("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"),
+            Some("Tron")
+        );
+        // Solana
+        assert_eq!(
+            which_cryptocurrency("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+            Some("Solana")
+        );
+        // Tezos
+        assert_eq!(
+            which_cryptocurrency("tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb"),
+            Some("Tezos")
+        );
+        // TON
+        assert_eq!(
+            which_cryptocurrency("EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N"),
+            Some("TON")
+        );
         // No coin identified
         assert_eq!(which_cryptocurrency("LQ4i7FLNbtJAFZq"), None);
     }
 
     #[test]
-    fn test_is_cryptocurrency_any() {
-        assert!(is_cryptocurrency_any("D6K2nqqQKycTucCSFSHhpiig4yQ6NPQRf9"));
-        assert!(!is_cryptocurrency_any("LQ4i7FLNbtJAFZq"));
+    fn test_which_cryptocurrency_any() {
+        assert!(which_cryptocurrency("D6K2nqqQKycTucCSFSHhpiig4yQ6NPQRf9").is_some());
+        assert!(which_cryptocurrency("LQ4i7FLNbtJAFZq").is_none());
+        // base58-looking tokens with no valid checksum
+        assert!(which_cryptocurrency("1SomeRandomBase58TokenWithDigits99").is_none());
+        assert!(which_cryptocurrency("AbCdEfGhJkLmNpQrStUvWxYz123456789a").is_none());
     }
 
     #[test]
     fn test_is_bitcoin() {
-        assert!(is_bitcoin("1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9"))
+        assert!(validate(
+            "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9",
+            &Type::Bitcoin
+        ));
+        assert!(validate(
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            &Type::Bitcoin
+        ));
+        assert!(validate(
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            &Type::Bitcoin
+        ));
+        assert!(validate(
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            &Type::Bitcoin
+        ));
+        assert!(validate(
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            &Type::Bitcoin
+        ));
+
+        // invalid checksums & other coins
+        assert!(!validate(
+            "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h8",
+            &Type::Bitcoin
+        ));
+        assert!(!validate(
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdp",
+            &Type::Bitcoin
+        ));
+        assert!(!validate(
+            "LQ4i7FLNhfCC9GXw682mS1NzvVKbtJAFZq",
+            &Type::Bitcoin
+        ));
+        assert!(!validate(
+            "1aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            &Type::Bitcoin
+        ));
     }
 
     #[test]
     fn test_is_bitcoin_cash() {
-        assert!(is_bitcoin_cash(
-            "qppjlghjlwg6tgxv7ffhvs43rlul0kpp4c0shk4dr6"
-        ))
+        assert!(validate(
+            "qppjlghjlwg6tgxv7ffhvs43rlul0kpp4c0shk4dr6",
+            &Type::BitcoinCash
+        ));
+        assert!(validate(
+            "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+            &Type::BitcoinCash
+        ));
+        assert!(!validate(
+            "qppjlghjlwg6tgxv7ffhvs43rlul0kpp4c0shk4dr7",
+            &Type::BitcoinCash
+        ));
+        assert!(!validate(
+            "1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9",
+            &Type::BitcoinCash
+        ));
     }
 
     #[test]
     fn test_is_ethereum() {
-        assert!(is_ethereum("0xaae47eae4ddd4877e0ae0bc780cfaee3cc3b52cb"))
+        assert!(validate(
+            "0xaae47eae4ddd4877e0ae0bc780cfaee3cc3b52cb",
+            &Type::Ethereum
+        ));
+        // EIP-55 test vectors
+        assert!(validate(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            &Type::Ethereum
+        ));
+        assert!(validate(
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            &Type::Ethereum
+        ));
+        assert!(validate(
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            &Type::Ethereum
+        ));
+        assert!(!validate(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD",
+            &Type::Ethereum
+        ));
+        assert!(!validate(
+            "0xaae47eae4ddd4877e0ae0bc780cfaee3cc3b52c",
+            &Type::Ethereum
+        ));
     }
 
     #[test]
     fn test_is_litecoin() {
-        assert!(is_litecoin("LQ4i7FLNhfCC9GXw682mS1NzvVKbtJAFZq"))
+        assert!(validate(
+            "LQ4i7FLNhfCC9GXw682mS1NzvVKbtJAFZq",
+            &Type::Litecoin
+        ));
+        assert!(validate(
+            "MA8ScdbcprVDsXNpdCjCrjf2VaPbDSxawU",
+            &Type::Litecoin
+        ));
+        assert!(validate(
+            "ltc1qf5gstfsgpdammct7t8072uev4yc69xrxdkaegn",
+            &Type::Litecoin
+        ));
+        assert!(!validate(
+            "ltc1qf5gstfsgpdammct7t8072uev4yc69xrxdkaegm",
+            &Type::Litecoin
+        ));
+        assert!(!validate(
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            &Type::Litecoin
+        ));
     }
 
     #[test]
     fn test_is_dogecoin() {
-        assert!(is_dogecoin("D6K2nqqQKycTucCSFSHhpiig4yQ6NPQRf9"))
+        assert!(validate("D6K2nqqQKycTucCSFSHhpiig4yQ6NPQRf9", &Type::Dodge));
+        assert!(!validate(
+            "AeHauBkGkHPTxh4PEUhNr7WRgivmcdCRnR",
+            &Type::Dodge
+        ));
     }
 
     #[test]
     fn test_is_dash() {
-        assert!(is_dash("XqLYPDTADW6EYuQmTcEAx81o8EHTKwqTK8"))
+        assert!(validate("XqLYPDTADW6EYuQmTcEAx81o8EHTKwqTK8", &Type::Dash));
+        assert!(validate("7fbz587n3dPUPnBW8YfzVhrtKugF97FRAY", &Type::Dash));
+        assert!(!validate("XqLYPDTADW6EYuQmTcEAx81o8EHTKwqTK9", &Type::Dash));
     }
 
     #[test]
     fn test_is_monero() {
-        assert!(is_monero("41gYNjXMeXaTmZFVv645A1HRVoA637cXFGbDdLV8Gn5hLvfxfRLKigUTvm2HVZhBzDVPeGpDy71qxASTpRFgepDwLexA8Ti"))
+        assert!(validate("41gYNjXMeXaTmZFVv645A1HRVoA637cXFGbDdLV8Gn5hLvfxfRLKigUTvm2HVZhBzDVPeGpDy71qxASTpRFgepDwLexA8Ti", &Type::Monero));
+        assert!(validate("44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A", &Type::Monero));
+        assert!(!validate("41gYNjXMeXaTmZFVv645A1HRVoA637cXFGbDdLV8Gn5hLvfxfRLKigUTvm2HVZhBzDVPeGpDy71qxASTpRFgepDwLexA8Tj", &Type::Monero));
     }
 
     #[test]
     fn test_is_neo() {
-        assert!(is_neo("AeHauBkGkHPTxh4PEUhNr7WRgivmcdCRnR"))
+        assert!(validate("AeHauBkGkHPTxh4PEUhNr7WRgivmcdCRnR", &Type::Neo));
+        assert!(!validate("AeHauBkGkHPTxh4PEUhNr7WRgivmcdCRnr", &Type::Neo));
     }
 
     #[test]
     fn test_is_ripple() {
-        assert!(is_ripple("rUocf1ixKzTuEe34kmVhRvGqNCofY1NJzV"));
-        assert!(!is_ripple("RegQueryValueExA"));
+        assert!(validate(
+            "rUocf1ixKzTuEe34kmVhRvGqNCofY1NJzV",
+            &Type::Ripple
+        ));
+        assert!(!validate("RegQueryValueExA", &Type::Ripple));
+        assert!(!validate(
+            "rUocf1ixKzTuEe34kmVhRvGqNCofY1NJzW",
+            &Type::Ripple
+        ));
+        assert!(!validate("randomStringThatLooksLikeRipple1", &Type::Ripple));
+    }
+
+    #[test]
+    fn test_is_tron() {
+        assert!(validate("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t", &Type::Tron));
+        assert!(!validate("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u", &Type::Tron));
+        assert!(!is_solana("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+    }
+
+    #[test]
+    fn test_is_solana() {
+        assert!(is_solana("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"));
+        // too short to be a 32-byte key
+        assert!(!is_solana("1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9"));
+        // no digits, like an ordinary identifier
+        assert!(!is_solana("AbstractSingletonProxyFactoryBeanWrapperImpl"));
+    }
+
+    #[test]
+    fn test_is_labelled_solana() {
+        let address = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        assert!(is_labelled_solana(address, "SOL:"));
+        assert!(is_labelled_solana(address, "solana"));
+        assert!(!is_labelled_solana(address, "id:"));
+        assert!(!is_labelled_solana(address, ""));
+        // random Base58 IDs decode to 32 bytes too
+        assert!(is_solana("5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"));
+        assert!(!is_labelled_solana(
+            "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d",
+            "tx:"
+        ));
+        assert!(!is_labelled_solana(
+            "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin",
+            "program"
+        ));
+    }
+
+    #[test]
+    fn test_is_cardano() {
+        // CIP-19 test vectors: base & enterprise addresses
+        assert!(validate("addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x", &Type::Cardano));
+        assert!(validate(
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+            &Type::Cardano
+        ));
+        assert!(!validate(
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl9",
+            &Type::Cardano
+        ));
+    }
+
+    #[test]
+    fn test_is_zcash() {
+        assert!(validate(
+            "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs",
+            &Type::Zcash
+        ));
+        assert!(validate(
+            "t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd",
+            &Type::Zcash
+        ));
+        assert!(validate(
+            "zs16nm8k8pmu5q4sshwpes99aedcxxnynv508t2ujy5xcag5nm6l4c2vnc3jvrukd9qlhsxj7zccxk",
+            &Type::Zcash
+        ));
+        assert!(validate("u18alh4rtnvhg4ngc59txa9uxnlcs2n9cvgjp3ccd7w4y0scuc83q9qhxvaptckall5w88l0mfvxa0yc5swgz4ht5dw0txm8tnfyh3tr65m73fhn7655amfvpzpa0sunyd0mcyswq2hun27mdz", &Type::Zcash));
+        assert!(!validate(
+            "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbt",
+            &Type::Zcash
+        ));
+    }
+
+    #[test]
+    fn test_is_polkadot() {
+        assert!(validate(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            &Type::Polkadot
+        ));
+        assert!(!validate(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp6",
+            &Type::Polkadot
+        ));
+    }
+
+    #[test]
+    fn test_is_cosmos() {
+        assert!(validate(
+            "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02",
+            &Type::Cosmos
+        ));
+        assert!(validate(
+            "cosmos10mg58ph84r7uyf6qx02sdmlu00567y5986yjyy",
+            &Type::Cosmos
+        ));
+        assert!(!validate(
+            "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd03",
+            &Type::Cosmos
+        ));
+    }
+
+    #[test]
+    fn test_is_tezos() {
+        assert!(validate(
+            "tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb",
+            &Type::Tezos
+        ));
+        assert!(validate(
+            "KT1PWx2mnDueood7fEmfbBDKx1D9BAnnXitn",
+            &Type::Tezos
+        ));
+        assert!(!validate(
+            "tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjc",
+            &Type::Tezos
+        ));
+    }
+
+    #[test]
+    fn test_is_stellar() {
+        assert!(validate(
+            "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7",
+            &Type::Stellar
+        ));
+        assert!(!validate(
+            "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN6",
+            &Type::Stellar
+        ));
+    }
+
+    #[test]
+    fn test_is_ton() {
+        assert!(validate(
+            "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N",
+            &Type::Ton
+        ));
+        assert!(validate(
+            "0:83dfd552e63729b472fcbcc8c45ebcc6691702558b68ec7527e1ba403a0f31a8",
+            &Type::Ton
+        ));
+        assert!(!validate(
+            "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2M",
+            &Type::Ton
+        ));
     }
 }
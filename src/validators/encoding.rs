@@ -0,0 +1,394 @@
+//! Decoders of the encodings used by crypto currency addresses & keys, with their checksums.
+use sha2::{Digest, Sha256};
+
+/// Base58 alphabet of Bitcoin & most other coins
+pub const BITCOIN_ALPHABET: &[u8; 58] =
+    b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Base58 alphabet of Ripple
+pub const RIPPLE_ALPHABET: &[u8; 58] =
+    b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+/// Characters of Bech32 & CashAddr, by value
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    //! Double SHA-256, as used by Base58Check
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+pub fn base58_decode(value: &str, alphabet: &[u8; 58]) -> Option<Vec<u8>> {
+    //! Decodes a Base58 string; each leading zero digit is a leading zero byte
+    let mut bytes: Vec<u8> = vec![];
+    for c in value.bytes() {
+        let mut carry = alphabet.iter().position(|x| *x == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = value.bytes().take_while(|c| *c == alphabet[0]).count();
+    let mut decoded = vec![0; zeros];
+    decoded.extend(bytes.into_iter().skip_while(|x| *x == 0));
+    Some(decoded)
+}
+
+pub fn base58check_decode(value: &str, alphabet: &[u8; 58]) -> Option<Vec<u8>> {
+    //! Decodes a Base58Check string & verifies its checksum (the first 4 bytes of the
+    //! double SHA-256 of the payload); returns the payload, version bytes included
+    let decoded = base58_decode(value, alphabet)?;
+    if decoded.len() < 5 {
+        return None;
+    }
+    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+    if sha256d(payload)[..4] != *checksum {
+        return None;
+    }
+    Some(payload.to_vec())
+}
+
+pub fn monero_base58_decode(value: &str) -> Option<Vec<u8>> {
+    //! Decodes the Base58 of Monero; blocks of 11 characters encode 8 bytes each,
+    //! & a shorter last block encodes fewer bytes
+    const BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+    if !value.is_ascii() {
+        return None;
+    }
+    let mut decoded = vec![];
+    for block in value.as_bytes().chunks(11) {
+        let size = BLOCK_SIZES.iter().position(|x| *x == block.len())?;
+        let mut number: u128 = 0;
+        for c in block {
+            let digit = BITCOIN_ALPHABET.iter().position(|x| x == c)?;
+            number = number * 58 + digit as u128;
+        }
+        if number >> (8 * size) != 0 {
+            return None;
+        }
+        decoded.extend_from_slice(&number.to_be_bytes()[16 - size..]);
+    }
+    Some(decoded)
+}
+
+/// Checksum variant of a Bech32 string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bech32 {
+    /// BIP-173
+    Bech32,
+    /// BIP-350
+    Bech32m,
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x1ffffff) << 5) ^ u32::from(value);
+        GENERATORS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+pub fn bech32_decode(value: &str) -> Option<(String, Bech32, Vec<u8>)> {
+    //! Decodes a Bech32 or Bech32m string & verifies its checksum; returns the human
+    //! readable part (lowercase), the variant & the 5-bit data values, without the checksum
+    let has_lower = value.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = value.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper || !value.is_ascii() {
+        return None;
+    }
+    let value = value.to_ascii_lowercase();
+    let (hrp, data) = value.rsplit_once('1')?;
+    if hrp.is_empty() || data.len() < 6 || hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return None;
+    }
+    let data = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|x| *x == c).map(|x| x as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    let expanded = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 31));
+    let variant = match bech32_polymod(expanded.chain(data.iter().copied())) {
+        1 => Bech32::Bech32,
+        0x2bc830a3 => Bech32::Bech32m,
+        _ => return None,
+    };
+    Some((hrp.to_string(), variant, data[..data.len() - 6].to_vec()))
+}
+
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    //! Regroups the bits of the given values, eg: from 5-bit Bech32 values to bytes
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut converted = vec![];
+    let max = (1 << to) - 1;
+    for value in data {
+        let value = u32::from(*value);
+        if value >> from != 0 {
+            return None;
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            converted.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+        return None;
+    }
+    Some(converted)
+}
+
+pub fn segwit_decode(value: &str, hrp: &str) -> Option<(u8, Vec<u8>)> {
+    //! Decodes a SegWit address (BIP-173 & BIP-350) of the given human readable part;
+    //! returns the witness version & program
+    if value.len() > 90 {
+        return None;
+    }
+    let (found, variant, data) = bech32_decode(value)?;
+    let (version, program) = data.split_first()?;
+    let program = convert_bits(program, 5, 8, false)?;
+    let valid = found == hrp
+        && (2..=40).contains(&program.len())
+        && match version {
+            0 => variant == Bech32::Bech32 && matches!(program.len(), 20 | 32),
+            1..=16 => variant == Bech32::Bech32m,
+            _ => false,
+        };
+    valid.then_some((*version, program))
+}
+
+pub fn cashaddr_verify(value: &str, prefix: &str) -> bool {
+    //! Verifies the checksum of a CashAddr address, with or without its prefix
+    const GENERATORS: [u64; 5] = [
+        0x98f2bc8e61,
+        0x79b76d99e2,
+        0xf33e5fb3c4,
+        0xae2eabe2a8,
+        0x1e4f43e470,
+    ];
+    let has_lower = value.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = value.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return false;
+    }
+    let value = value.to_ascii_lowercase();
+    let (found, payload) = value.split_once(':').unwrap_or((prefix, &value));
+    if found != prefix || payload.len() < 8 {
+        return false;
+    }
+    let payload = match payload
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|x| *x == c).map(|x| x as u8))
+        .collect::<Option<Vec<u8>>>()
+    {
+        Some(payload) => payload,
+        None => return false,
+    };
+    let values = prefix
+        .bytes()
+        .map(|c| c & 31)
+        .chain(std::iter::once(0))
+        .chain(payload);
+    let polymod = values.fold(1u64, |checksum, value| {
+        let top = checksum >> 35;
+        let checksum = ((checksum & 0x07_ffff_ffff) << 5) ^ u64::from(value);
+        GENERATORS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    });
+    polymod ^ 1 == 0
+}
+
+pub fn base32_decode(value: &str) -> Option<Vec<u8>> {
+    //! Decodes an unpadded, uppercase Base32 string (RFC 4648), eg: Stellar addresses
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut decoded = vec![];
+    for c in value.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        acc = (acc << 5) | u32::from(digit);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((acc >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+pub fn base64_decode(value: &str) -> Option<Vec<u8>> {
+    //! Decodes an unpadded Base64 string, of either the standard or the URL-safe alphabet
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut decoded = vec![];
+    for c in value.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        acc = (acc << 6) | u32::from(digit);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((acc >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+pub fn base64_encode(data: &[u8]) -> String {
+    //! Encodes bytes as unpadded, standard Base64, eg: for OpenSSH fingerprints
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |acc, (i, byte)| {
+            acc | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    encoded
+}
+
+pub fn der_read(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    //! Reads a DER element (single byte tags only, as in X.509); returns its tag,
+    //! its content & the data after it
+    let (&tag, rest) = data.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = match first {
+        0..=0x7f => (usize::from(first), rest),
+        0x81..=0x84 => {
+            let n = usize::from(first & 0x7f);
+            if rest.len() < n {
+                return None;
+            }
+            let len = rest[..n]
+                .iter()
+                .fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte));
+            (len, &rest[n..])
+        }
+        _ => return None,
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+pub fn crc16_xmodem(data: &[u8]) -> u16 {
+    //! CRC-16/XMODEM checksum, as used by Stellar & TON addresses
+    data.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58check_decode() {
+        let payload = base58check_decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", BITCOIN_ALPHABET);
+        assert_eq!(payload.unwrap()[0], 0);
+        assert!(
+            base58check_decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb", BITCOIN_ALPHABET).is_none()
+        );
+        assert!(base58check_decode("0OIl", BITCOIN_ALPHABET).is_none());
+        assert_eq!(
+            base58_decode("111", BITCOIN_ALPHABET).unwrap(),
+            vec![0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_segwit_decode() {
+        // BIP-173 & BIP-350 test vectors
+        let (version, program) =
+            segwit_decode("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", "bc").unwrap();
+        assert_eq!((version, program.len()), (0, 20));
+        assert!(segwit_decode(
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            "bc"
+        )
+        .is_some());
+        // bech32 checksum with witness version 1
+        assert!(segwit_decode(
+            "bc1pznuztv4mcvka35vkxel6samgwvrfcy4gj4xcmf6382nhqnwap84sf5938q",
+            "bc"
+        )
+        .is_none());
+        // mixed case
+        assert!(segwit_decode("bc1qW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", "bc").is_none());
+        // wrong human readable part
+        assert!(segwit_decode("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", "ltc").is_none());
+    }
+
+    #[test]
+    fn test_cashaddr_verify() {
+        let address = "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a";
+        assert!(cashaddr_verify(address, "bitcoincash"));
+        assert!(cashaddr_verify(&address[12..], "bitcoincash"));
+        assert!(cashaddr_verify(&address.to_uppercase(), "bitcoincash"));
+        assert!(!cashaddr_verify(
+            &address.replace("6a", "6b"),
+            "bitcoincash"
+        ));
+        assert!(!cashaddr_verify(&address[12..], "bchtest"));
+    }
+
+    #[test]
+    fn test_base32_base64_decode() {
+        assert_eq!(base32_decode("MZXW6YQ").unwrap(), b"foob");
+        assert_eq!(base64_decode("Zm9vYg").unwrap(), b"foob");
+        assert_eq!(base64_decode("-_8").unwrap(), base64_decode("+/8").unwrap());
+        assert!(base32_decode("mzxw6yq").is_none());
+        assert!(base64_decode("Zm9v=").is_none());
+        assert_eq!(crc16_xmodem(b"123456789"), 0x31c3);
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+    }
+
+    #[test]
+    fn test_der_read() {
+        let (tag, content, rest) = der_read(&[0x30, 0x03, 0x02, 0x01, 0x05, 0xff]).unwrap();
+        assert_eq!(
+            (tag, content, rest),
+            (0x30, &[0x02, 0x01, 0x05][..], &[0xff][..])
+        );
+        let long = [&[0x04, 0x81, 0x80][..], &[0; 128]].concat();
+        assert_eq!(der_read(&long).unwrap().1.len(), 128);
+        assert!(der_read(&[0x30, 0x05, 0x02]).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+use fancy_regex::Regex;
+
+lazy_static! {
+    /// MD5 Regex Pattern
+    static ref MD5: Regex = Regex::new(r"(?i)^[a-f0-9]{32}$").unwrap();
+    /// SHA-1 Regex Pattern
+    static ref SHA1: Regex = Regex::new(r"(?i)^[a-f0-9]{40}$").unwrap();
+    /// SHA-256 Regex Pattern
+    static ref SHA256: Regex = Regex::new(r"(?i)^[a-f0-9]{64}$").unwrap();
+    /// SHA-512 Regex Pattern
+    static ref SHA512: Regex = Regex::new(r"(?i)^[a-f0-9]{128}$").unwrap();
+    /// SSDEEP Regex Pattern (blocksize:hash:hash)
+    static ref SSDEEP: Regex = Regex::new(r"^\d{1,10}:[a-zA-Z0-9/+]{3,64}:[a-zA-Z0-9/+]{3,64}$").unwrap();
+    /// TLSH Regex Pattern (with or without the T1 version prefix)
+    static ref TLSH: Regex = Regex::new(r"(?i)^(?:T1)?[a-f0-9]{70}$").unwrap();
+}
+
+enum Type {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+    Ssdeep,
+    Tlsh,
+}
+
+impl Type {
+    fn name<'a>(&self) -> &'a str {
+        match *self {
+            Type::Md5 => "MD5",
+            Type::Sha1 => "SHA-1",
+            Type::Sha256 => "SHA-256",
+            Type::Sha512 => "SHA-512",
+            Type::Ssdeep => "SSDEEP",
+            Type::Tlsh => "TLSH",
+        }
+    }
+
+    fn pattern<'a>(&self) -> &'a Regex {
+        match *self {
+            Type::Md5 => &MD5,
+            Type::Sha1 => &SHA1,
+            Type::Sha256 => &SHA256,
+            Type::Sha512 => &SHA512,
+            Type::Ssdeep => &SSDEEP,
+            Type::Tlsh => &TLSH,
+        }
+    }
+
+    fn all() -> Vec<Type> {
+        vec![
+            Type::Md5,
+            Type::Sha1,
+            Type::Sha256,
+            Type::Sha512,
+            Type::Ssdeep,
+            Type::Tlsh,
+        ]
+    }
+}
+
+/// Evaluate Hash & Validate
+fn validate(value: &str, hash: &Type) -> bool {
+    // a hex digest made of just letters is most likely a word, not a hash
+    !value.is_empty()
+        && value.chars().any(|c| c.is_ascii_digit())
+        && hash.pattern().is_match(value).unwrap_or_default()
+}
+
+pub fn is_md5(value: &str) -> bool {
+    //! Check if the given value is a MD5 hash.
+    validate(value, &Type::Md5)
+}
+
+pub fn is_imphash(value: &str, label: &str) -> bool {
+    //! Check if the given value is an imphash; an imphash is a MD5 hash of the
+    //! imports of a PE file, so it can only be told apart by its label (eg: `imphash:`).
+    is_md5(value)
+        && label
+            .trim_end_matches([':', '='])
+            .eq_ignore_ascii_case("imphash")
+}
+
+pub fn is_hash_any(value: &str) -> bool {
+    //! Check if the given value is any of the known hashes.
+    Type::all().iter().any(|hash| validate(value, hash))
+}
+
+pub fn which_hash(value: &str) -> Option<&str> {
+    //! Output the Hash algorithm name given the hash.
+    for hash in Type::all() {
+        if validate(value, &hash) {
+            return Some(hash.name());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_which_hash() {
+        assert_eq!(which_hash("d41d8cd98f00b204e9800998ecf8427e"), Some("MD5"));
+        assert_eq!(
+            which_hash("DA39A3EE5E6B4B0D3255BFEF95601890AFD80709"),
+            Some("SHA-1")
+        );
+        assert_eq!(
+            which_hash("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            Some("SHA-256")
+        );
+        assert_eq!(which_hash("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"), Some("SHA-512"));
+        assert_eq!(
+            which_hash("96:s4Ud1Lj96tHHlZDrwciQmA+4uy1I0G4HYuL8N3TzS8QsO/wqWXLcMSx:sF1LjEtHHlZDrwciQmA+4uy1I0G4HYuLH"),
+            Some("SSDEEP")
+        );
+        assert_eq!(
+            which_hash("T1A0D1F0A2E5B1C0A2E5B1C0A2E5B1C0A2E5B1C0A2E5B1C0A2E5B1C0A2E5B1C0A2E5B1C0"),
+            Some("TLSH")
+        );
+        assert_eq!(which_hash("not-a-hash"), None);
+    }
+
+    #[test]
+    fn test_is_md5() {
+        assert!(is_md5("d41d8cd98f00b204e9800998ecf8427e"));
+        assert!(!is_md5("d41d8cd98f00b204e9800998ecf8427"));
+        assert!(!is_md5("g41d8cd98f00b204e9800998ecf8427e"));
+        assert!(!is_md5("abcdefabcdefabcdefabcdefabcdefab"));
+    }
+
+    #[test]
+    fn test_is_sha1() {
+        assert!(validate(
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            &Type::Sha1
+        ));
+        assert!(!validate(
+            "0xda39a3ee5e6b4b0d3255bfef95601890afd80709",
+            &Type::Sha1
+        ));
+    }
+
+    #[test]
+    fn test_is_sha256() {
+        assert!(validate(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            &Type::Sha256
+        ));
+    }
+
+    #[test]
+    fn test_is_ssdeep() {
+        assert!(validate(
+            "3:AXGBicFlgVNhBGcL6wCrFQEv:AXGHsNhxLsr2C",
+            &Type::Ssdeep
+        ));
+        assert!(!validate("3:AXGBicFlgVNhBGcL6wCrFQEv", &Type::Ssdeep));
+        assert!(!validate("10:20:30", &Type::Ssdeep));
+    }
+
+    #[test]
+    fn test_is_imphash() {
+        assert!(is_imphash("f34d5f2d4577ed6d9ceec516c1f5a744", "imphash:"));
+        assert!(is_imphash("f34d5f2d4577ed6d9ceec516c1f5a744", "ImpHash"));
+        assert!(!is_imphash("f34d5f2d4577ed6d9ceec516c1f5a744", "md5:"));
+    }
+}
@@ -1,3 +1,4 @@
+use super::network::normalize_ipv4;
 use fancy_regex::Regex;
 use idna::domain_to_ascii;
 
@@ -64,6 +65,10 @@ lazy_static! {
             format!("{}{}", *IP_MIDDLE_OCTET, r"{2}").as_str(),
             format!("{})", *IP_LAST_OCTET).as_str(),
             r"|",
+            // IPv4 address in integer, hex or octal notation, eg: 3232235777 or 0xC0A80101
+            // (validated & normalised by `network::normalize_ipv4`)
+            r"(?P<obfuscated_ip>(?:0x[0-9a-f]+|\d+)(?:\.(?:0x[0-9a-f]+|\d+)){0,3})",
+            r"|",
             // IPv6 RegEx from https://stackoverflow.com/a/17871737
             r"\[(",
             // 1:2:3:4:5:6:7:8
@@ -118,15 +123,11 @@ lazy_static! {
     ).unwrap();
 }
 
-fn is_domain_valid(domain: &str) -> bool {
-    if NOT_DOMAINS
+pub fn is_domain_valid(domain: &str) -> bool {
+    //! Checks to see if a Given Domain is not on the list of names that are never domains
+    !NOT_DOMAINS
         .iter()
         .any(|dom| domain.to_lowercase().eq(&dom.to_lowercase()))
-    {
-        false
-    } else {
-        true
-    }
 }
 
 fn is_tld_valid(domain: &str) -> bool {
@@ -139,7 +140,7 @@ fn is_tld_valid(domain: &str) -> bool {
         let tld = parts[1].to_lowercase();
         if tld
             .chars()
-            .map(|c| (c.is_alphabetic() || c == '.'))
+            .map(|c| c.is_alphabetic() || c == '.')
             .any(|x| !x)
         {
             // make sure that we dont
@@ -203,13 +204,13 @@ pub fn is_domain(value: &str) -> bool {
             // this looks like a .co.com or a .co.uk, etc tld's..
             // or that it could also have sub tld's
             // most likely the false positive in here might be less
-            return is_domain_valid(&x)
+            return is_domain_valid(&x);
         } else {
             // some logic to reduce false positives,
             // needs fine-tuning as we see more false positives
             // will miss out on legitimate 2-letter / 3-letter domains
             if x.len() > 7 {
-                return is_domain_valid(&x)
+                return is_domain_valid(&x);
             }
         }
     }
@@ -217,17 +218,42 @@ pub fn is_domain(value: &str) -> bool {
     false
 }
 
+pub fn has_known_tld(value: &str) -> bool {
+    //! Check if the given Domain Name ends with a known TLD.
+    match domain_to_ascii(value) {
+        Ok(x) => DOMAINS_EXT.iter().any(|suffix| x.ends_with(suffix)),
+        Err(_) => false,
+    }
+}
+
 pub fn is_url(value: &str) -> bool {
     //! Check if the given value is a URL.
-    URL.is_match(value).unwrap_or_default()
+    get_url(value).is_some()
 }
 
 pub fn get_url(value: &str) -> Option<String> {
     //! Extracts the URL from a given string.
-    let val = URL
-        .find(value)
+    let captures = URL
+        .captures(value)
         .expect("Regex failed to operate on input!")?;
-    Some(val.as_str().to_string())
+    if let Some(host) = captures.name("obfuscated_ip") {
+        normalize_ipv4(host.as_str())?;
+    }
+    captures.get(0).map(|x| x.as_str().to_string())
+}
+
+pub fn deobfuscate_url(url: &str) -> Option<String> {
+    //! The URL with its IPv4 host in dotted-quad notation, if it is written in
+    //! an obfuscated notation (eg: `http://0xC0A80101/`); None otherwise.
+    let captures = URL.captures(url).ok()??;
+    let host = captures.name("obfuscated_ip")?;
+    let ip = normalize_ipv4(host.as_str())?;
+    Some(format!(
+        "{}{}{}",
+        &url[..host.start()],
+        ip,
+        &url[host.end()..]
+    ))
 }
 
 #[cfg(test)]
@@ -305,6 +331,29 @@ mod tests {
         assert!(!is_url("abc.com"));
         assert!(!is_url("localhost"));
         assert!(!is_url("localhost:9455"));
+        assert!(!is_url("http://4294967296/"));
+        assert!(!is_url("http://0.1.2.3/"));
+    }
+
+    #[test]
+    fn test_deobfuscate_url() {
+        for url in [
+            "http://3232235777/",
+            "http://0xC0A80101/a.php?x=1",
+            "https://0300.0250.1.1:8443",
+        ] {
+            assert!(is_url(url), "{}", url);
+        }
+        assert_eq!(
+            deobfuscate_url("http://0xC0A80101/a.php?x=1").unwrap(),
+            "http://192.168.1.1/a.php?x=1"
+        );
+        assert_eq!(
+            deobfuscate_url("http://user@3232235777:80/").unwrap(),
+            "http://user@192.168.1.1:80/"
+        );
+        assert_eq!(deobfuscate_url("http://192.168.1.1/"), None);
+        assert_eq!(deobfuscate_url("http://123.example.com/"), None);
     }
 
     #[test]
@@ -1,6 +1,11 @@
-#![allow(dead_code)]
-
+pub mod attack;
+pub mod block;
 pub mod crypto;
+pub mod encoding;
+pub mod hash;
 pub mod internet;
 pub mod network;
+pub mod secret;
 pub mod system;
+pub mod vulnerability;
+pub mod wallet;
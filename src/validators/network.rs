@@ -1,12 +1,135 @@
-use std::{net::IpAddr, str::FromStr};
+use super::internet;
+use crate::indicator::IpClass;
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// Special purpose IPv4 blocks (RFC 6890 & the IANA registry); the first match wins
+const IPV4_BLOCKS: [(Ipv4Addr, u8, IpClass); 15] = [
+    (Ipv4Addr::new(0, 0, 0, 0), 8, IpClass::Bogon),
+    (Ipv4Addr::new(10, 0, 0, 0), 8, IpClass::Private),
+    (Ipv4Addr::new(100, 64, 0, 0), 10, IpClass::Cgnat),
+    (Ipv4Addr::new(127, 0, 0, 0), 8, IpClass::Loopback),
+    (Ipv4Addr::new(169, 254, 0, 0), 16, IpClass::LinkLocal),
+    (Ipv4Addr::new(172, 16, 0, 0), 12, IpClass::Private),
+    (Ipv4Addr::new(192, 0, 0, 0), 24, IpClass::Bogon),
+    (Ipv4Addr::new(192, 0, 2, 0), 24, IpClass::Documentation),
+    (Ipv4Addr::new(192, 88, 99, 0), 24, IpClass::Reserved),
+    (Ipv4Addr::new(192, 168, 0, 0), 16, IpClass::Private),
+    (Ipv4Addr::new(198, 18, 0, 0), 15, IpClass::Bogon),
+    (Ipv4Addr::new(198, 51, 100, 0), 24, IpClass::Documentation),
+    (Ipv4Addr::new(203, 0, 113, 0), 24, IpClass::Documentation),
+    (Ipv4Addr::new(224, 0, 0, 0), 4, IpClass::Multicast),
+    (Ipv4Addr::new(240, 0, 0, 0), 4, IpClass::Reserved),
+];
+
+/// Special purpose IPv6 blocks; the first match wins
+const IPV6_BLOCKS: [(Ipv6Addr, u8, IpClass); 9] = [
+    (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 128, IpClass::Bogon),
+    (
+        Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1),
+        128,
+        IpClass::Loopback,
+    ),
+    (
+        Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0),
+        64,
+        IpClass::Bogon,
+    ),
+    (
+        Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+        32,
+        IpClass::Documentation,
+    ),
+    (
+        Ipv6Addr::new(0x3fff, 0, 0, 0, 0, 0, 0, 0),
+        20,
+        IpClass::Documentation,
+    ),
+    (
+        Ipv6Addr::new(0x2000, 0, 0, 0, 0, 0, 0, 0),
+        3,
+        IpClass::Public,
+    ),
+    (
+        Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 0),
+        7,
+        IpClass::Private,
+    ),
+    (
+        Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0),
+        10,
+        IpClass::LinkLocal,
+    ),
+    (
+        Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0),
+        8,
+        IpClass::Multicast,
+    ),
+];
+
+/// Check to see if a given IP Address is in the network of the given prefix length.
+pub fn is_in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Classify an IP Address by the special purpose block it is in; Public if in none.
+/// IPv4-mapped & NAT64 IPv6 Addresses are classified by the IPv4 Address they embed,
+/// and IPv6 Addresses outside of the global unicast block (2000::/3) are Reserved.
+pub fn classify_ip(ip: IpAddr) -> IpClass {
+    match ip {
+        IpAddr::V4(ip) => IPV4_BLOCKS
+            .iter()
+            .find(|(network, prefix, _)| {
+                is_in_network(IpAddr::V4(ip), IpAddr::V4(*network), *prefix)
+            })
+            .map_or(IpClass::Public, |(_, _, class)| *class),
+        IpAddr::V6(ip) => {
+            let nat64 = Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0, 0);
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return classify_ip(IpAddr::V4(v4));
+            }
+            if is_in_network(IpAddr::V6(ip), IpAddr::V6(nat64), 96) {
+                let [.., a, b, c, d] = ip.octets();
+                return classify_ip(IpAddr::V4(Ipv4Addr::new(a, b, c, d)));
+            }
+            IPV6_BLOCKS
+                .iter()
+                .find(|(network, prefix, _)| {
+                    is_in_network(IpAddr::V6(ip), IpAddr::V6(*network), *prefix)
+                })
+                .map_or(IpClass::Reserved, |(_, _, class)| *class)
+        }
+    }
+}
+
+/// Check to see if a given value is an IP Address or CIDR & return its class;
+/// a CIDR is classified by its network address. Addresses in 0.0.0.0/8 ("this network")
+/// are never the address of a host, so they have no class & are not indicators.
+pub fn which_ip_class(value: &str) -> Option<IpClass> {
+    let ip = value.split('/').next()?;
+    if !is_ipv_any(ip) {
+        return None;
+    }
+    match IpAddr::from_str(ip).ok()? {
+        IpAddr::V4(ip) if ip.octets()[0] == 0 => None,
+        ip => Some(classify_ip(ip)),
+    }
+}
 
 /// Check to see if a given value corresponds to IPv4 Address.
 pub fn is_ipv4(value: &str) -> bool {
-    // 0., 1. & 2. ip subnets are reserved blocks of IANA & APNIC & RIPE NCC
-    // https://en.wikipedia.org/wiki/List_of_assigned_/8_IPv4_address_blocks
-    if value.starts_with("2.") || value.starts_with("1.") || value.starts_with("0.") {
-        return false;
-    }
     let ip = if let Ok(ipaddr) = IpAddr::from_str(value) {
         ipaddr
     } else {
@@ -15,6 +138,67 @@ pub fn is_ipv4(value: &str) -> bool {
     ip.is_ipv4()
 }
 
+fn parse_ipv4_part(part: &str) -> Option<u64> {
+    //! A number of a (non-canonical) IPv4 Address; hex with `0x`, octal with a leading 0
+    let (digits, radix) = match part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None if part.len() > 1 && part.starts_with('0') => (&part[1..], 8),
+        None => (part, 10),
+    };
+    if digits.is_empty() || digits.len() > 11 || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Check to see if a given value is an IPv4 Address in a non-canonical notation (as accepted
+/// by `inet_aton` & browsers: integer, hex, octal, dotted-hex or with less than 4 parts,
+/// eg: `3232235777`, `0xC0A80101`, `0300.0250.1.1`) & return it in dotted-quad notation.
+pub fn normalize_ipv4(value: &str) -> Option<String> {
+    if is_ipv4(value) {
+        return None;
+    }
+    let parts: Vec<&str> = value.split('.').collect();
+    if parts.len() > 4 {
+        return None;
+    }
+    let numbers = parts
+        .iter()
+        .map(|part| parse_ipv4_part(part))
+        .collect::<Option<Vec<u64>>>()?;
+    let (last, init) = numbers.split_last()?;
+    // the last number fills the remaining bytes, eg: 10.1 is 10.0.0.1
+    if init.iter().any(|x| *x > 255) || *last >= 1 << (8 * (5 - parts.len())) {
+        return None;
+    }
+    let ip = init
+        .iter()
+        .enumerate()
+        .fold(*last, |ip, (i, x)| ip | x << (24 - 8 * i));
+    Some(Ipv4Addr::from(ip as u32).to_string())
+}
+
+/// Check to see if a given value is an IPv4 Address written in an obfuscated notation:
+/// 4 parts with a hex one, or an octal one that reads differently in decimal (eg:
+/// `0xC0.0xA8.1.1`, `0300.0250.1.1`), or a full 32-bit hex number (eg: `0xC0A80101`).
+/// Plain integers, shortened forms & zero-padded version numbers (eg: `01.02.03.04`) are
+/// too ambiguous outside of URLs.
+pub fn is_ipv4_obfuscated(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    let hex =
+        |part: &&str| part.len() > 2 && part.get(..2).is_some_and(|x| x.eq_ignore_ascii_case("0x"));
+    // 01 to 07 are the same in octal & decimal
+    let octal = |part: &&str| {
+        part.len() > 1 && part.starts_with('0') && parse_ipv4_part(part).is_some_and(|x| x >= 8)
+    };
+    let form = match parts.as_slice() {
+        [number] => number.len() == 10 && hex(number),
+        [_, _, _, _] => parts.iter().any(|part| hex(part) || octal(part)),
+        _ => false,
+    };
+    form && normalize_ipv4(value).is_some()
+}
+
 /// Check to see if a given IPv4 Address with CIDR is valid.
 pub fn is_ipv4_cidr(value: &str) -> bool {
     let splitted_groups: Vec<&str> = value.splitn(2, '/').collect();
@@ -81,16 +265,6 @@ pub fn is_ipv6_cidr(value: &str) -> bool {
     true
 }
 
-/// Check to see if a given value corresponds to Local/loopback IP Address.
-pub fn is_ip_loopback(value: &str) -> bool {
-    let ip = if let Ok(ipaddr) = IpAddr::from_str(value) {
-        ipaddr
-    } else {
-        return false;
-    };
-    ip.is_loopback()
-}
-
 /// Check to see if a given value corresponds to any IP Address.
 pub fn is_ipv_any(value: &str) -> bool {
     if is_ipv4(value) || is_ipv6(value) {
@@ -117,6 +291,32 @@ pub fn which_ipv(value: &str) -> Option<&str> {
     None
 }
 
+/// Check to see if a given value is a `host:port` pair & return the host & the port (1-65535);
+/// an IPv6 host is in brackets (eg: `[2001:db8::1]:443`) & returned without them.
+pub fn split_host_port(value: &str) -> Option<(&str, u16)> {
+    let (host, port) = value.rsplit_once(':')?;
+    if host.is_empty() || port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let port = port.parse::<u16>().ok().filter(|x| *x > 0)?;
+    match host.strip_prefix('[') {
+        Some(ipv6) => Some((ipv6.strip_suffix(']')?, port)),
+        // a bare IPv6 Address has no port, eg: fe80::1:443
+        None if host.contains(':') => None,
+        None => Some((host, port)),
+    }
+}
+
+/// Check to see if a given value is an endpoint: an IPv4 Address, an IPv6 Address in
+/// brackets or a Domain Name, with a port.
+pub fn is_endpoint(value: &str) -> bool {
+    match split_host_port(value) {
+        Some((host, _)) if value.starts_with('[') => is_ipv6(host),
+        Some((host, _)) => is_ipv4(host) || internet::is_domain(host),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,14 +328,52 @@ mod tests {
         assert!(is_ipv4("100.128.10.132"));
         assert!(is_ipv4("100.17.5.119"));
         assert!(is_ipv4("127.0.0.1"));
+        assert!(is_ipv4("1.1.1.1"));
+        assert!(is_ipv4("2.56.10.1"));
+        assert!(is_ipv4("0.1.2.0"));
 
         // invalid
         assert!(!is_ipv4("12.110.105.256"));
         assert!(!is_ipv4("10.2.13"));
         assert!(!is_ipv4("256.10.10.1000"));
-        assert!(!is_ipv4("2.1.2.0"));
-        assert!(!is_ipv4("1.1.2.0"));
-        assert!(!is_ipv4("0.1.2.0"));
+    }
+
+    #[test]
+    fn test_which_ip_class() {
+        for (ip, class) in [
+            ("1.1.1.1", IpClass::Public),
+            ("2.56.10.1", IpClass::Public),
+            ("10.0.0.1", IpClass::Private),
+            ("172.31.255.255", IpClass::Private),
+            ("172.32.0.1", IpClass::Public),
+            ("192.168.1.0/24", IpClass::Private),
+            ("127.0.0.1", IpClass::Loopback),
+            ("169.254.1.1", IpClass::LinkLocal),
+            ("100.64.0.1", IpClass::Cgnat),
+            ("192.0.2.10", IpClass::Documentation),
+            ("203.0.113.0/24", IpClass::Documentation),
+            ("224.0.0.251", IpClass::Multicast),
+            ("240.0.0.1", IpClass::Reserved),
+            ("255.255.255.255", IpClass::Reserved),
+            ("198.18.0.1", IpClass::Bogon),
+            ("2606:4700:4700::1111", IpClass::Public),
+            ("2001:db8::1", IpClass::Documentation),
+            ("2001:0DB8:1234::/48", IpClass::Documentation),
+            ("::1", IpClass::Loopback),
+            ("::", IpClass::Bogon),
+            ("fd00::1", IpClass::Private),
+            ("fe80::1", IpClass::LinkLocal),
+            ("ff02::1", IpClass::Multicast),
+            ("::ffff:10.0.0.1", IpClass::Private),
+            ("64:ff9b::808:808", IpClass::Public),
+            ("4000::1", IpClass::Reserved),
+        ] {
+            assert_eq!(which_ip_class(ip), Some(class), "{}", ip);
+        }
+        assert_eq!(which_ip_class("10.0.0"), None);
+        assert_eq!(which_ip_class("0.1.2.0"), None);
+        assert_eq!(which_ip_class("0.0.0.0/8"), None);
+        assert_eq!(which_ip_class("::ffff:0.1.2.0"), Some(IpClass::Bogon));
     }
 
     #[test]
@@ -169,14 +407,6 @@ mod tests {
         assert!(!is_ipv6_cidr("2002:::1234::/48"));
     }
 
-    #[test]
-    fn test_is_ip_loopback() {
-        assert!(is_ip_loopback("127.0.0.1"));
-        assert!(is_ip_loopback("::1"));
-        assert!(!is_ip_loopback("10.122.1.130"));
-        assert!(!is_ip_loopback("::ffff:7f00:1"));
-    }
-
     #[test]
     fn test_is_ipv_any() {
         // valid
@@ -204,4 +434,62 @@ mod tests {
         assert_eq!(which_ipv("127.0.0.1"), Some("IPv4"));
         assert_eq!(which_ipv("2002:::1234::"), None);
     }
+
+    #[test]
+    fn test_is_endpoint() {
+        // valid
+        assert!(is_endpoint("1.2.3.4:8080"));
+        assert!(is_endpoint("[2001:db8::1]:443"));
+        assert!(is_endpoint("www.evil-domain.com:65535"));
+        assert_eq!(split_host_port("[::1]:22"), Some(("::1", 22)));
+
+        // invalid
+        assert!(!is_endpoint("1.2.3.4:0"));
+        assert!(!is_endpoint("1.2.3.4:65536"));
+        assert!(!is_endpoint("1.2.3.4:"));
+        assert!(!is_endpoint("2001:db8::1:443"));
+        assert!(!is_endpoint("[1.2.3.4]:80"));
+        assert!(!is_endpoint("host:1.2.3.4:80"));
+        assert!(!is_endpoint("12:30"));
+    }
+
+    #[test]
+    fn test_normalize_ipv4() {
+        for (value, ip) in [
+            ("3232235777", "192.168.1.1"),
+            ("0xC0A80101", "192.168.1.1"),
+            ("0300.0250.1.1", "192.168.1.1"),
+            ("0xc0.0xa8.0x1.0x1", "192.168.1.1"),
+            ("0xC0.168.257", "192.168.1.1"),
+            ("127.1", "127.0.0.1"),
+            ("017700000001", "127.0.0.1"),
+        ] {
+            assert_eq!(normalize_ipv4(value).as_deref(), Some(ip), "{}", value);
+        }
+        assert_eq!(normalize_ipv4("192.168.1.1"), None);
+        assert_eq!(normalize_ipv4("4294967296"), None);
+        assert_eq!(normalize_ipv4("1.2.3.256"), None);
+        assert_eq!(normalize_ipv4("08.1.1.1"), None);
+        assert_eq!(normalize_ipv4("1.2.3.4.5"), None);
+        assert_eq!(normalize_ipv4("0x"), None);
+        assert_eq!(normalize_ipv4("+1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_is_ipv4_obfuscated() {
+        // valid
+        assert!(is_ipv4_obfuscated("0xC0A80101"));
+        assert!(is_ipv4_obfuscated("0300.0250.1.1"));
+        assert!(is_ipv4_obfuscated("0xC0.0xA8.0x01.0x01"));
+
+        // invalid
+        assert!(!is_ipv4_obfuscated("3232235777"));
+        assert!(!is_ipv4_obfuscated("0x7f"));
+        assert!(!is_ipv4_obfuscated("127.1"));
+        assert!(!is_ipv4_obfuscated("192.168.1.1"));
+        assert!(!is_ipv4_obfuscated("0300.0250.1.256"));
+        assert!(!is_ipv4_obfuscated("1.0.0.01"));
+        assert!(!is_ipv4_obfuscated("01.02.03.04"));
+        assert!(!is_ipv4_obfuscated("10.0.0.07"));
+    }
 }
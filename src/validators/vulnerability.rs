@@ -0,0 +1,128 @@
+use fancy_regex::Regex;
+
+lazy_static! {
+    /// CVE Regex Pattern (CVE-YYYY-NNNN...)
+    static ref CVE: Regex = Regex::new(r"(?i)^CVE-(\d{4})-(\d{4,})$").unwrap();
+    /// CWE Regex Pattern
+    static ref CWE: Regex = Regex::new(r"(?i)^CWE-[1-9]\d{0,5}$").unwrap();
+    /// CAPEC Regex Pattern
+    static ref CAPEC: Regex = Regex::new(r"(?i)^CAPEC-[1-9]\d{0,5}$").unwrap();
+    /// GitHub Security Advisory Regex Pattern
+    static ref GHSA: Regex = Regex::new(
+        r"(?i)^GHSA(-[23456789cfghjmpqrvwx]{4}){3}$"
+    ).unwrap();
+}
+
+enum Type {
+    Cve,
+    Cwe,
+    Capec,
+    Ghsa,
+}
+
+impl Type {
+    fn name<'a>(&self) -> &'a str {
+        match *self {
+            Type::Cve => "CVE",
+            Type::Cwe => "CWE",
+            Type::Capec => "CAPEC",
+            Type::Ghsa => "GHSA",
+        }
+    }
+
+    fn pattern<'a>(&self) -> &'a Regex {
+        match *self {
+            Type::Cve => &CVE,
+            Type::Cwe => &CWE,
+            Type::Capec => &CAPEC,
+            Type::Ghsa => &GHSA,
+        }
+    }
+
+    fn all() -> Vec<Type> {
+        vec![Type::Cve, Type::Cwe, Type::Capec, Type::Ghsa]
+    }
+}
+
+pub fn is_cve(value: &str) -> bool {
+    //! Check if the given value is a CVE Identifier.
+    //! The year must be 1999 (the first CVE year) or later, and the sequence number must
+    //! have at least 4 digits; longer sequence numbers must not have leading zeros.
+    let caps = match CVE.captures(value) {
+        Ok(Some(caps)) => caps,
+        _ => return false,
+    };
+    let year: i64 = caps[1].parse().unwrap_or_default();
+    let sequence = &caps[2];
+    year >= 1999 && (sequence.len() == 4 || !sequence.starts_with('0'))
+}
+
+pub fn which_vulnerability(value: &str) -> Option<&str> {
+    //! Output the kind of Vulnerability Identifier (CVE, CWE, CAPEC, GHSA).
+    for vulnerability in Type::all() {
+        let valid = match vulnerability {
+            Type::Cve => is_cve(value),
+            _ => vulnerability.pattern().is_match(value).unwrap_or_default(),
+        };
+        if valid {
+            return Some(vulnerability.name());
+        }
+    }
+    None
+}
+
+pub fn normalize_vulnerability(value: &str) -> Option<String> {
+    //! Normalise a Vulnerability Identifier; upper-case, except for the
+    //! body of GHSA Identifiers which are lower-case (eg: `GHSA-jfh8-c2jp-5v3q`).
+    match which_vulnerability(value)? {
+        "GHSA" => Some(format!("GHSA{}", value[4..].to_lowercase())),
+        _ => Some(value.to_uppercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_cve() {
+        // valid
+        assert!(is_cve("CVE-2021-44228"));
+        assert!(is_cve("cve-2014-0160"));
+        assert!(is_cve("CVE-1999-0001"));
+        assert!(is_cve("CVE-2024-123456"));
+        // any later year, so that the result does not depend on the system clock
+        assert!(is_cve("CVE-3021-44228"));
+
+        // invalid
+        assert!(!is_cve("CVE-2021-442"));
+        assert!(!is_cve("CVE-1998-0001"));
+        assert!(!is_cve("CVE-2021-01234"));
+        assert!(!is_cve("CVE-21-44228"));
+    }
+
+    #[test]
+    fn test_which_vulnerability() {
+        assert_eq!(which_vulnerability("CVE-2021-44228"), Some("CVE"));
+        assert_eq!(which_vulnerability("CWE-79"), Some("CWE"));
+        assert_eq!(which_vulnerability("capec-66"), Some("CAPEC"));
+        assert_eq!(which_vulnerability("GHSA-jfh8-c2jp-5v3q"), Some("GHSA"));
+        assert_eq!(which_vulnerability("GHSA-jfh8-c2jp-5v3"), None);
+        assert_eq!(which_vulnerability("GHSA-abcd-c2jp-5v3q"), None);
+        assert_eq!(which_vulnerability("CWE-079"), None);
+    }
+
+    #[test]
+    fn test_normalize_vulnerability() {
+        assert_eq!(
+            normalize_vulnerability("cve-2021-44228").unwrap(),
+            "CVE-2021-44228"
+        );
+        assert_eq!(normalize_vulnerability("Cwe-79").unwrap(), "CWE-79");
+        assert_eq!(
+            normalize_vulnerability("ghsa-JFH8-C2JP-5V3Q").unwrap(),
+            "GHSA-jfh8-c2jp-5v3q"
+        );
+        assert_eq!(normalize_vulnerability("CVE-2021"), None);
+    }
+}
@@ -0,0 +1,209 @@
+use super::encoding::{base58check_decode, BITCOIN_ALPHABET};
+use fancy_regex::Regex;
+use sha2::{Digest, Sha256};
+
+lazy_static! {
+    /// Extended Public Key Regex Pattern (BIP-32, xpub/ypub/zpub & their testnet forms)
+    static ref XPUB: Regex = Regex::new(r"^[xyzYZtuvUV]pub[1-9A-HJ-NP-Za-km-z]{107,108}$").unwrap();
+    /// Extended Private Key Regex Pattern (BIP-32, xprv/yprv/zprv & their testnet forms)
+    static ref XPRV: Regex = Regex::new(r"^[xyzYZtuvUV]prv[1-9A-HJ-NP-Za-km-z]{107,108}$").unwrap();
+    /// WIF Private Key Regex Pattern (uncompressed & compressed)
+    static ref WIF: Regex = Regex::new(r"^(?:5[HJK][1-9A-HJ-NP-Za-km-z]{49}|[KL][1-9A-HJ-NP-Za-km-z]{51})$").unwrap();
+    /// BIP-39 Mnemonic Regex Pattern (12, 15, 18, 21 or 24 words)
+    static ref MNEMONIC: Regex = Regex::new(r"^[a-z]{3,8}(?: [a-z]{3,8}){11,23}$").unwrap();
+
+    /// BIP-39 English word list, sorted
+    static ref WORDLIST: Vec<&'static str> = include_str!("bip39_english.txt").lines().collect();
+}
+
+/// Version bytes of the extended public keys
+const XPUB_VERSIONS: [[u8; 4]; 8] = [
+    [0x04, 0x88, 0xb2, 0x1e], // xpub
+    [0x04, 0x9d, 0x7c, 0xb2], // ypub
+    [0x04, 0xb2, 0x47, 0x46], // zpub
+    [0x02, 0x95, 0xb4, 0x3f], // Ypub
+    [0x02, 0xaa, 0x7e, 0xd3], // Zpub
+    [0x04, 0x35, 0x87, 0xcf], // tpub
+    [0x04, 0x4a, 0x52, 0x62], // upub
+    [0x04, 0x5f, 0x1c, 0xf6], // vpub
+];
+
+/// Version bytes of the extended private keys
+const XPRV_VERSIONS: [[u8; 4]; 8] = [
+    [0x04, 0x88, 0xad, 0xe4], // xprv
+    [0x04, 0x9d, 0x78, 0x78], // yprv
+    [0x04, 0xb2, 0x43, 0x0c], // zprv
+    [0x02, 0x95, 0xb0, 0x05], // Yprv
+    [0x02, 0xaa, 0x7a, 0x99], // Zprv
+    [0x04, 0x35, 0x83, 0x94], // tprv
+    [0x04, 0x4a, 0x4e, 0x28], // uprv
+    [0x04, 0x5f, 0x18, 0xbc], // vprv
+];
+
+enum Type {
+    ExtendedPublicKey,
+    ExtendedPrivateKey,
+    Wif,
+    Mnemonic,
+}
+
+impl Type {
+    fn name<'a>(&self) -> &'a str {
+        match *self {
+            Type::ExtendedPublicKey => "Extended Public Key",
+            Type::ExtendedPrivateKey => "Extended Private Key",
+            Type::Wif => "WIF Private Key",
+            Type::Mnemonic => "BIP-39 Mnemonic",
+        }
+    }
+
+    fn pattern<'a>(&self) -> &'a Regex {
+        match *self {
+            Type::ExtendedPublicKey => &XPUB,
+            Type::ExtendedPrivateKey => &XPRV,
+            Type::Wif => &WIF,
+            Type::Mnemonic => &MNEMONIC,
+        }
+    }
+
+    fn verify(&self, value: &str) -> bool {
+        //! Decodes the value & verifies its checksum & version
+        match *self {
+            // the key data of a public key is a compressed point (0x02 or 0x03)
+            Type::ExtendedPublicKey => extended_key(value, &XPUB_VERSIONS)
+                .is_some_and(|payload| matches!(payload[45], 0x02 | 0x03)),
+            // & of a private key, a 0x00 byte followed by the key
+            Type::ExtendedPrivateKey => {
+                extended_key(value, &XPRV_VERSIONS).is_some_and(|payload| payload[45] == 0x00)
+            }
+            Type::Wif => base58check_decode(value, BITCOIN_ALPHABET).is_some_and(|payload| {
+                payload[0] == 0x80
+                    && (payload.len() == 33 || (payload.len() == 34 && payload[33] == 0x01))
+            }),
+            Type::Mnemonic => is_mnemonic_checksum(value),
+        }
+    }
+
+    fn all() -> Vec<Type> {
+        vec![
+            Type::ExtendedPublicKey,
+            Type::ExtendedPrivateKey,
+            Type::Wif,
+            Type::Mnemonic,
+        ]
+    }
+}
+
+fn extended_key(value: &str, versions: &[[u8; 4]]) -> Option<Vec<u8>> {
+    //! The 78-byte payload of a Base58Check extended key of one of the given versions
+    let payload = base58check_decode(value, BITCOIN_ALPHABET)?;
+    (payload.len() == 78 && versions.iter().any(|x| payload.starts_with(x))).then_some(payload)
+}
+
+fn is_mnemonic_checksum(value: &str) -> bool {
+    //! Verifies that all words are on the word list & the checksum; each word holds 11 bits,
+    //! & 1 of every 33 bits (at the end) is a checksum: the first bits of the SHA-256 of the rest
+    let mut bits = vec![];
+    for word in value.split(' ') {
+        let index = match WORDLIST.binary_search(&word) {
+            Ok(index) => index,
+            Err(_) => return false,
+        };
+        bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+    }
+    if bits.len() % 33 != 0 {
+        return false;
+    }
+    let checksum_len = bits.len() / 33;
+    let (entropy, checksum) = bits.split_at(bits.len() - checksum_len);
+    let entropy: Vec<u8> = entropy
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, bit| (acc << 1) | u8::from(*bit)))
+        .collect();
+    let hash = Sha256::digest(&entropy);
+    checksum
+        .iter()
+        .enumerate()
+        .all(|(i, bit)| ((hash[0] >> (7 - i)) & 1 == 1) == *bit)
+}
+
+/// Evaluate Crypto Wallet Secret & Validate
+fn validate(value: &str, secret: &Type) -> bool {
+    secret.pattern().is_match(value).unwrap_or_default() && secret.verify(value)
+}
+
+pub fn is_bip39_word(value: &str) -> bool {
+    //! Check if the given word is on the BIP-39 English word list.
+    WORDLIST.binary_search(&value).is_ok()
+}
+
+pub fn is_mnemonic(value: &str) -> bool {
+    //! Check if the given words (separated by a single space) are a BIP-39 Mnemonic.
+    validate(value, &Type::Mnemonic)
+}
+
+pub fn which_crypto_secret(value: &str) -> Option<&str> {
+    //! Output the kind of Crypto Wallet Secret given the value.
+    Type::all()
+        .into_iter()
+        .find(|secret| validate(value, secret))
+        .map(|secret| secret.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_which_crypto_secret() {
+        // BIP-32 test vector 1
+        assert_eq!(which_crypto_secret("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"), Some("Extended Public Key"));
+        assert_eq!(which_crypto_secret("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"), Some("Extended Private Key"));
+        assert_eq!(
+            which_crypto_secret("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+            Some("WIF Private Key")
+        );
+        assert_eq!(
+            which_crypto_secret(
+                "legal winner thank year wave sausage worth useful legal winner thank yellow"
+            ),
+            Some("BIP-39 Mnemonic")
+        );
+        assert_eq!(
+            which_crypto_secret("1GiWxH6PzSSmbdcK72XfGpqhjSb6nae6h9"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_extended_key() {
+        // BIP-84 account key
+        assert!(validate("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs", &Type::ExtendedPublicKey));
+        assert!(!validate("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet9", &Type::ExtendedPublicKey));
+        assert!(!validate("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8", &Type::ExtendedPrivateKey));
+    }
+
+    #[test]
+    fn test_is_wif() {
+        assert!(validate(
+            "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617",
+            &Type::Wif
+        ));
+        assert!(!validate(
+            "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98618",
+            &Type::Wif
+        ));
+    }
+
+    #[test]
+    fn test_is_mnemonic() {
+        assert!(is_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+        assert!(is_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"));
+        // invalid checksum
+        assert!(!is_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"));
+        // not on the word list
+        assert!(!is_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon bitcoin"));
+        // 13 words
+        assert!(!is_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+    }
+}
@@ -1,140 +1,493 @@
-use crate::validators::{
-    crypto::{self, which_cryptocurrency},
-    internet, network, system,
+use crate::{
+    defang,
+    extractor::Extractor,
+    indicator::{Category, Indicator},
+    matches::{Match, Source},
+    validators::{
+        attack, block,
+        crypto::{self, which_cryptocurrency},
+        hash::{self, which_hash},
+        internet, network, secret, system, vulnerability, wallet,
+    },
 };
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct NewlineResult {
-    pub registry_keys: Option<Vec<String>>,
-    pub sql: Option<Vec<String>>,
-    pub file_paths: Option<Vec<String>>,
+    pub registry_keys: Option<Vec<Match>>,
+    pub sql: Option<Vec<Match>>,
+    pub file_paths: Option<Vec<Match>>,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct WhitespaceResult {
-    pub urls: Option<Vec<String>>,
-    pub domains: Option<Vec<String>>,
-    pub emails: Option<Vec<String>>,
-    pub ip_address: Option<Vec<String>>,
-    pub crypto: Option<Vec<String>>,
-    pub regexes: Option<Vec<String>>,
+    pub urls: Option<Vec<Match>>,
+    pub domains: Option<Vec<Match>>,
+    pub emails: Option<Vec<Match>>,
+    pub ip_address: Option<Vec<Match>>,
+    pub endpoints: Option<Vec<Match>>,
+    pub crypto: Option<Vec<Match>>,
+    pub crypto_secrets: Option<Vec<Match>>,
+    pub secrets: Option<Vec<Match>>,
+    pub blocks: Option<Vec<Match>>,
+    pub hashes: Option<Vec<Match>>,
+    pub vulnerabilities: Option<Vec<Match>>,
+    pub attack_ids: Option<Vec<Match>>,
+    pub regexes: Option<Vec<Match>>,
 }
 
-pub fn by_newline(s: String) -> NewlineResult {
+pub fn by_newline(s: &str, extractor: &Extractor) -> NewlineResult {
+    let src = Source::new(s, extractor.get_context());
+    let on = |category| extractor.is_enabled(category);
     let mut registry = vec![];
     let mut sql = vec![];
     let mut file_paths = vec![];
 
     // check for registry keys & sql queries by breaking only newlines
-    for x in s.split('\n').collect::<Vec<&str>>() {
+    for x in s.split('\n') {
         let x = x.trim();
-        if system::is_registry_key(x) {
-            registry.push(x.to_string())
-        } else if system::is_sql(x) {
-            sql.push(x.to_string())
-        } else if system::is_file_path(x) {
-            file_paths.push(x.to_string())
+        let start = src.offset_of(x);
+        let end = start + x.len();
+        if extractor.is_too_long(x) {
+            continue;
+        }
+        if on(Category::RegistryKeys) && system::is_registry_key(x) {
+            registry.push(src.locate(x.to_string(), start, end))
+        } else if on(Category::Sql) && system::is_sql(x) {
+            sql.push(src.locate(x.to_string(), start, end))
+        } else if on(Category::FilePaths) && system::is_file_path(x) {
+            file_paths.push(src.locate(x.to_string(), start, end))
         }
     }
 
     NewlineResult {
-        registry_keys: if !registry.is_empty() {
-            registry.sort();
-            registry.dedup();
-            Some(registry)
-        } else {
-            None
-        },
-        sql: if !sql.is_empty() {
-            sql.sort();
-            sql.dedup();
-            Some(sql)
-        } else {
-            None
-        },
-        file_paths: if !file_paths.is_empty() {
-            file_paths.sort();
-            file_paths.dedup();
-            Some(file_paths)
-        } else {
-            None
-        },
+        registry_keys: extractor.limited(Category::RegistryKeys, registry),
+        sql: extractor.limited(Category::Sql, sql),
+        file_paths: extractor.limited(Category::FilePaths, file_paths),
     }
 }
 
-pub fn by_whitespace(s: String) -> WhitespaceResult {
+/// Characters that separate indicators within a token, eg: `src=10.0.0.1,dst=10.0.0.2`
+const DELIMITERS: [char; 12] = ['=', ',', ';', '|', '"', '\'', '`', '(', ')', '<', '>', '{'];
+
+fn trim(token: &str) -> &str {
+    //! Strips the quotes, brackets & trailing punctuation around a token;
+    //! square brackets only when unbalanced, to keep defanged `[.]` & `[::1]` intact
+    let mut x = token;
+    loop {
+        let len = x.len();
+        x = x
+            .trim_start_matches(['"', '\'', '`', '(', '<', '{'])
+            .trim_end_matches(['"', '\'', '`', ')', '>', '}', '.', ',', ';', ':', '!', '?']);
+        let (open, close) = (x.matches('[').count(), x.matches(']').count());
+        if open > close {
+            x = x.strip_prefix('[').unwrap_or(x);
+        } else if close > open {
+            x = x.strip_suffix(']').unwrap_or(x);
+        } else if let Some(inner) = x.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+            if !inner.contains(['[', ']']) {
+                x = inner;
+            }
+        }
+        if x.len() == len {
+            return x;
+        }
+    }
+}
+
+fn secret_match(src: &Source, value: &str, provider: &str, start: usize) -> Match {
+    //! Builds the `Match` of a Secret; masked afterwards if the Extractor redacts
+    src.typed(
+        Indicator::secret(value, provider),
+        start,
+        start + value.len(),
+    )
+}
+
+fn query_values(url: &str) -> impl Iterator<Item = &str> {
+    //! The values of the query parameters of a URL, eg: `AIza...` in `?key=AIza...`
+    let query = url.split_once('?').map_or("", |x| x.1);
+    let query = query.split('#').next().unwrap_or_default();
+    query
+        .split('&')
+        .filter_map(|x| x.split_once('=').map(|x| x.1))
+        .filter(|x| !x.is_empty())
+}
+
+fn hash_indicator(value: &str, algorithm: &str) -> Indicator {
+    //! The Indicator of a File Hash of the given algorithm
+    Indicator::Hash {
+        value: value.to_string(),
+        algorithm: algorithm.to_string(),
+    }
+}
+
+fn assignment<'a>(token: &'a str, label: &'a str, before: &'a str) -> Option<(&'a str, &'a str)> {
+    //! The name & value of a setting or variable assigned in the given token, or across it
+    //! & the previous tokens; eg: `name=value`, `"name": "value"` or `name = value`
+    let is_operator = |x: &str| x == "=" || x == ":";
+    let (name, value) = match token.find(['=', ':']) {
+        Some(i) if !token[i..].starts_with("://") && !token.ends_with(['=', ':']) => {
+            (&token[..i], &token[i + 1..])
+        }
+        Some(_) => return None,
+        None if is_operator(label) => (before, token),
+        None => (label.strip_suffix([':', '='])?, token),
+    };
+    let name = trim(name);
+    let value = trim(value.trim_start_matches(['=', ' ']));
+    let identifier = |x: &str| {
+        !x.is_empty()
+            && x.bytes()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'.'))
+    };
+    (identifier(name) && !value.is_empty()).then_some((name, value))
+}
+
+fn find_mnemonics(src: &Source, words: &[&str], labelled: bool) -> Option<Match> {
+    //! Finds the BIP-39 mnemonic in a run of consecutive BIP-39 words; English text often
+    //! has runs of BIP-39 words, so the whole run must be the mnemonic, unless it follows
+    //! a label (eg: `seed:`), in which case the longest valid mnemonic it starts with is kept
+    let len = [24, 21, 18, 15, 12]
+        .into_iter()
+        .filter(|len| {
+            if labelled {
+                *len <= words.len()
+            } else {
+                *len == words.len()
+            }
+        })
+        .find(|len| wallet::is_mnemonic(&words[..*len].join(" ")))?;
+    let start = src.offset_of(words[0]);
+    let last = words[len - 1];
+    let end = src.offset_of(last) + last.len();
+    let indicator = Indicator::CryptoSecret {
+        value: words[..len].join(" "),
+        kind: "BIP-39 Mnemonic".to_string(),
+    };
+    Some(src.typed(indicator, start, end))
+}
+
+fn is_mnemonic_label(token: &str) -> bool {
+    //! Check if a token labels the mnemonic that follows it, eg: `seed:` or `Mnemonic`;
+    //! `seed` & `phrase` are BIP-39 words, so they are labels only with a `:` or `=`
+    let word = trim(token).trim_end_matches('=');
+    let labelled = token.ends_with([':', '=']);
+    word.eq_ignore_ascii_case("mnemonic")
+        || (labelled && (word.eq_ignore_ascii_case("seed") || word.eq_ignore_ascii_case("phrase")))
+}
+
+pub fn by_whitespace(s: &str, extractor: &Extractor) -> WhitespaceResult {
+    let src = Source::new(s, extractor.get_context());
+    let on = |category| extractor.is_enabled(category);
     let mut urls = vec![];
     let mut domains = vec![];
     let mut emails = vec![];
     let mut ip_address = vec![];
+    let mut endpoints = vec![];
     let mut crypto_address = vec![];
+    let mut crypto_secrets = vec![];
+    let mut mnemonics = vec![];
+    let mut secrets = vec![];
+    let mut blocks = vec![];
+    let mut hashes = vec![];
+    let mut vulnerabilities = vec![];
+    let mut attack_ids = vec![];
     let mut regexes = vec![];
 
-    // check for the rest by breaking newlines, whitespace, tabs, etc...
-    for x in s.split_whitespace().collect::<Vec<&str>>() {
-        let x = x.trim();
+    // PEM blocks span several tokens & lines; their tokens are not checked any further
+    let mut spans = vec![];
+    for (_, range) in block::pem_blocks(s) {
+        let value = &s[range.clone()];
+        if secret::is_private_key(value) {
+            if on(Category::Secrets) {
+                secrets.push(secret_match(&src, value, "Private Key", range.start));
+            }
+        } else if let Some(kind) = block::which_block(value) {
+            let indicator = Indicator::block(value, kind);
+            // the domains a certificate is for, eg: `www.example.com` & `*.example.com`
+            let certificate = match &indicator {
+                Indicator::Block {
+                    certificate: Some(certificate),
+                    ..
+                } if on(Category::Domains) => certificate.domains.clone(),
+                _ => vec![],
+            };
+            if on(Category::Blocks) {
+                blocks.push(src.typed(indicator, range.start, range.end));
+            }
+            for domain in certificate {
+                let domain = domain.trim_start_matches("*.");
+                if internet::is_domain(domain) {
+                    domains.push(src.locate(domain.to_string(), range.start, range.end));
+                }
+            }
+        } else {
+            continue;
+        }
+        spans.push(range);
+    }
+
+    // checks a token, or a part of it, against the validators; true if anything was found
+    let mut check = |token: &str, label: &str| -> bool {
+        let start = src.offset_of(token);
+        let end = start + token.len();
+        // defanged urls, domains, emails & ips are validated in their refanged form
+        let refanged = defang::refang(token);
+        let x = refanged.as_deref().unwrap_or(token);
+        let found = |value: String, start: usize, end: usize| {
+            let mut m = src.locate(value, start, end);
+            m.defanged = refanged.is_some();
+            m
+        };
+        // the token is classified first & only then reported if its category is enabled,
+        // so that a disabled category does not hand its tokens over to the next validators
+        // addresses without a class (eg: 0.1.2.3) are not indicators, as in `validate::ip`
         if network::is_ipv_any(x) || network::is_ip_cidr_any(x) {
-            ip_address.push(x.to_string())
-        } else if crypto::is_cryptocurrency_any(x) {
-            if let Some(coin) = which_cryptocurrency(x) {
-                crypto_address.push(format!("{} - {}", x, coin))
+            if on(Category::IpAddress) && network::which_ip_class(x).is_some() {
+                ip_address.push(found(x.to_string(), start, end))
+            }
+        } else if let Some(ip) =
+            network::normalize_ipv4(x).filter(|_| network::is_ipv4_obfuscated(x))
+        {
+            if on(Category::IpAddress) && network::which_ip_class(&ip).is_some() {
+                let mut m = found(ip, start, end);
+                m.obfuscated = true;
+                ip_address.push(m)
+            }
+        } else if hash::is_hash_any(token) {
+            // hashes are checked before crypto addresses, as hex
+            // digests would otherwise look like crypto addresses
+            let algorithm = if hash::is_imphash(token, label) {
+                "Imphash"
+            } else if let Some(algorithm) = which_hash(token) {
+                algorithm
             } else {
-                crypto_address.push("".to_string())
+                return false;
+            };
+            if on(Category::Hashes) {
+                hashes.push(src.typed(hash_indicator(token, algorithm), start, end))
+            }
+        } else if let Some(id) = vulnerability::normalize_vulnerability(token) {
+            if on(Category::Vulnerabilities) {
+                vulnerabilities.push(src.locate(id, start, end))
+            }
+        } else if attack::is_attack_id(token) {
+            if on(Category::AttackIds)
+                && (!extractor.only_known_attack_ids() || attack::is_known_attack_id(token))
+            {
+                attack_ids.push(src.locate(token.to_string(), start, end))
+            }
+        } else if let Some(coin) = which_cryptocurrency(token).filter(|x| *x != "Solana") {
+            if on(Category::Crypto) {
+                let indicator = Indicator::Crypto {
+                    value: token.to_string(),
+                    coin: coin.to_string(),
+                };
+                crypto_address.push(src.typed(indicator, start, end))
+            }
+        } else if let Some(kind) = wallet::which_crypto_secret(token) {
+            if on(Category::CryptoSecrets) {
+                let indicator = Indicator::CryptoSecret {
+                    value: token.to_string(),
+                    kind: kind.to_string(),
+                };
+                crypto_secrets.push(src.typed(indicator, start, end))
+            }
+        } else if let Some(provider) = secret::which_secret(token) {
+            if on(Category::Secrets) {
+                secrets.push(secret_match(&src, token, provider, start))
+            }
+        } else if crypto::is_labelled_solana(token, label) {
+            // Solana addresses have no checksum, so only labelled ones are reported
+            if on(Category::Crypto) {
+                let indicator = Indicator::Crypto {
+                    value: token.to_string(),
+                    coin: "Solana".to_string(),
+                };
+                crypto_address.push(src.typed(indicator, start, end))
             }
         } else if internet::is_domain(x) {
-            domains.push(x.to_string())
+            if on(Category::Domains) {
+                domains.push(found(x.to_string(), start, end))
+            }
         } else if let Some(url) = internet::get_url(x) {
-            urls.push(url)
+            // secrets of known providers passed in the query, eg: `?key=AIza...`
+            if on(Category::Secrets) {
+                for value in query_values(&url) {
+                    if let (Some(provider), Some(i)) =
+                        (secret::which_secret(value), token.find(value))
+                    {
+                        secrets.push(secret_match(&src, value, provider, start + i))
+                    }
+                }
+            }
+            if on(Category::Urls) {
+                let mut m = if refanged.is_some() {
+                    found(url, start, end)
+                } else {
+                    let start = start + x.find(&url).unwrap_or_default();
+                    let end = start + url.len();
+                    found(url, start, end)
+                };
+                // URLs with an obfuscated IPv4 host are kept with it in dotted-quad notation
+                if let Some(url) = internet::deobfuscate_url(&m.value) {
+                    m.value = url;
+                    m.obfuscated = true;
+                }
+                urls.push(m)
+            }
         } else if internet::is_email(x, None) {
-            emails.push(x.to_string())
-        } else if system::is_regex(x) {
-            regexes.push(x.to_string())
+            if on(Category::Emails) {
+                emails.push(found(x.to_string(), start, end))
+            }
+        } else {
+            return false;
+        }
+        true
+    };
+
+    // the previous token, eg: `imphash:` labelling the hash that follows it
+    let mut label = "";
+    // the token before the previous one, eg: `password` in `password = ...`
+    let mut last_label = "";
+    let mut assigned = vec![];
+    // the run of consecutive BIP-39 words that the current token is part of & whether
+    // it follows a label
+    let mut words = vec![];
+    let mut labelled = false;
+
+    // check for the rest by breaking newlines, whitespace, tabs, etc...
+    for token in s.split_whitespace() {
+        let token = token.trim();
+        if spans.iter().any(|x| x.contains(&src.offset_of(token))) {
+            continue;
+        }
+        if on(Category::CryptoSecrets) {
+            let word = trim(token);
+            if wallet::is_bip39_word(word) && !is_mnemonic_label(token) {
+                words.push(word);
+            } else {
+                mnemonics.extend(find_mnemonics(&src, &words, labelled));
+                words.clear();
+                labelled = is_mnemonic_label(token);
+            }
+        }
+        let before = std::mem::replace(&mut last_label, label);
+        if extractor.is_too_long(token) {
+            label = token;
+            continue;
         }
+        // OpenSSH public keys are the key type & the key, eg: `ssh-ed25519 AAAA... user@host`
+        if block::is_ssh_key_type(label) {
+            let key = format!("{} {}", label, token);
+            if block::is_ssh_public_key(&key) {
+                if on(Category::Blocks) {
+                    let start = src.offset_of(label);
+                    let end = src.offset_of(token) + token.len();
+                    let indicator = Indicator::block(&key, "SSH Public Key");
+                    blocks.push(src.typed(indicator, start, end));
+                }
+                label = token;
+                continue;
+            }
+        }
+        // values that look random assigned to a secret setting, eg: `DB_PASSWORD=...`;
+        // unless the value is a secret of a known provider
+        if let Some((_, value)) = assignment(token, label, before).filter(|(name, value)| {
+            secret::is_generic_secret(value, name) && secret::which_secret(value).is_none()
+        }) {
+            if on(Category::Secrets) {
+                let start = src.offset_of(value);
+                assigned.push(secret_match(&src, value, "Generic Secret", start));
+            }
+            label = token;
+            continue;
+        }
+        if check(token, label) {
+            label = token;
+            continue;
+        }
+        // regular expressions are only ever whole tokens, as they are full of punctuation
+        if system::is_regex(token) {
+            if on(Category::Regexes) {
+                let start = src.offset_of(token);
+                regexes.push(src.locate(token.to_string(), start, start + token.len()));
+            }
+            label = token;
+            continue;
+        }
+        // look for indicators inside the token, eg: `(10.0.0.1)`, `ip=10.0.0.1,` or `evil.com.`
+        let trimmed = trim(token);
+        if trimmed.is_empty() || (trimmed.len() < token.len() && check(trimmed, label)) {
+            label = token;
+            continue;
+        }
+        let mut previous = label;
+        for part in trimmed
+            .split(DELIMITERS)
+            .map(trim)
+            .filter(|x| !x.is_empty())
+        {
+            let mut host = network::split_host_port(part).map_or(part, |(host, _)| host);
+            let mut found = host.len() < token.len() && check(host, previous);
+            // the endpoint may follow a label or a scheme, eg: `host:1.2.3.4:443` or
+            // `tcp://1.2.3.4:443`
+            let mut endpoint = part;
+            if !found {
+                let rest = part.split_once("://").map_or(part, |(_, x)| x);
+                let rest = match rest.split_once(':') {
+                    Some((_, x)) if network::split_host_port(rest).is_none() => x,
+                    _ => rest,
+                };
+                if let Some((x, _)) = network::split_host_port(rest) {
+                    if rest.len() < part.len() && check(x, previous) {
+                        (host, found) = (x, true);
+                    }
+                    endpoint = rest;
+                }
+            }
+            if on(Category::Endpoints) && network::split_host_port(endpoint).is_some() {
+                let refanged = defang::refang(endpoint);
+                let x = refanged.as_deref().unwrap_or(endpoint);
+                if network::is_endpoint(x) {
+                    let start = src.offset_of(endpoint);
+                    let mut m = src.locate(x.to_string(), start, start + endpoint.len());
+                    m.defanged = refanged.is_some();
+                    endpoints.push(m);
+                }
+            }
+            // eg: `host:10.0.0.1`, `sha256:<digest>` or `mailto:john@example.com`
+            let colons = host.matches(':').count();
+            if !found && (1..=2).contains(&colons) && !host.contains("//") {
+                for x in host.split(':').map(trim).filter(|x| !x.is_empty()) {
+                    check(x, previous);
+                    previous = x;
+                }
+            }
+            previous = part;
+        }
+        label = token;
     }
+    mnemonics.extend(find_mnemonics(&src, &words, labelled));
+    crypto_secrets.extend(mnemonics);
+    crypto_secrets.sort_by_key(|m| m.start);
+    secrets.extend(assigned);
+    secrets.sort_by_key(|m| m.start);
 
     WhitespaceResult {
-        urls: if !urls.is_empty() {
-            urls.sort();
-            urls.dedup();
-            Some(urls)
-        } else {
-            None
-        },
-        domains: if !domains.is_empty() {
-            domains.sort();
-            domains.dedup();
-            Some(domains)
-        } else {
-            None
-        },
-        emails: if !emails.is_empty() {
-            emails.sort();
-            emails.dedup();
-            Some(emails)
-        } else {
-            None
-        },
-        ip_address: if !ip_address.is_empty() {
-            ip_address.sort();
-            ip_address.dedup();
-            Some(ip_address)
-        } else {
-            None
-        },
-        crypto: if !crypto_address.is_empty() {
-            crypto_address.sort();
-            crypto_address.dedup();
-            Some(crypto_address)
-        } else {
-            None
-        },
-        regexes: if !regexes.is_empty() {
-            regexes.sort();
-            regexes.dedup();
-            Some(regexes)
-        } else {
-            None
-        },
+        urls: extractor.limited(Category::Urls, urls),
+        domains: extractor.limited(Category::Domains, domains),
+        emails: extractor.limited(Category::Emails, emails),
+        ip_address: extractor.limited(Category::IpAddress, ip_address),
+        endpoints: extractor.limited(Category::Endpoints, endpoints),
+        crypto: extractor.limited(Category::Crypto, crypto_address),
+        crypto_secrets: extractor.limited(Category::CryptoSecrets, crypto_secrets),
+        secrets: extractor.limited(Category::Secrets, secrets),
+        blocks: extractor.limited(Category::Blocks, blocks),
+        hashes: extractor.limited(Category::Hashes, hashes),
+        vulnerabilities: extractor.limited(Category::Vulnerabilities, vulnerabilities),
+        attack_ids: extractor.limited(Category::AttackIds, attack_ids),
+        regexes: extractor.limited(Category::Regexes, regexes),
     }
 }